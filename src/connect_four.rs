@@ -1,8 +1,11 @@
-use crate::core::game::{Position, Oracle};
-use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use crate::core::game::{AlternatingGame, DeterministicGame, Game, GameOutcome, IllegalMoveError, Position, Oracle, UnmakePosition};
+use crate::core::move_ordering::{MoveOrderer, OrderingContext};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator, SideToMovePositionEvaluator};
 use crate::core::r#move::{Move, MoveGenerator};
+use crate::core::transposition_table::PositionHash;
+use crate::core::zobrist::{ZobristKeys, ZobristPosition};
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum Piece {
     Red,
     Black,
@@ -10,10 +13,25 @@ pub enum Piece {
     Phantom // a fake piece that's never used, so our Oracles aren't confused into thinking a position is terminal
 }
 
+impl Piece {
+    /// The piece's index in the Zobrist keys, if it's a piece that can actually be on the board.
+    fn zobrist_index(&self) -> Option<usize> {
+        match self {
+            Piece::Red => Some(0),
+            Piece::Black => Some(1),
+            _ => None
+        }
+    }
+}
+
+static ZOBRIST_KEYS: ZobristKeys<42, 2> = ZobristKeys::new(0x636f6e6e65637434);
+
 #[derive(Copy, Clone, Debug)]
 pub struct ConnectFourPosition {
     board: [Piece; 42],
-    last_player: usize
+    last_player: usize,
+    // the side to move follows from the board, so only the pieces are hashed
+    hash: u64
 }
 
 impl ConnectFourPosition {
@@ -23,96 +41,78 @@ impl ConnectFourPosition {
             // the board in column-major order. 0 is the upper-left corner, and 1-5 are the cells below it.
             // 6-13 are column 2, etc.
             board: [Piece::None; 42],
-            last_player: 1
+            last_player: 1,
+            hash: 0
         }
     }
-}
 
-impl Position<ConnectFourPosition, ConnectFourMove> for ConnectFourPosition {
-    fn apply(&self, m: &ConnectFourMove) -> ConnectFourPosition {
-        let mut new_position = self.clone();
-        new_position.last_player = match m.piece {
-            Piece::Red => 0,
-            _ => 1
-        };
-
-        // drop the piece into position in the chosen column
-        for i in (m.column*6)..((m.column+1)*6-1) {
-            if new_position.board[i+1] != Piece::None {
-                new_position.board[i] = m.piece;
-                return new_position;
-            }
+    /// Put a piece in a cell, keeping the hash in step with the board.
+    fn place(&mut self, index: usize, piece: Piece) {
+        if let Some(old_piece) = self.board[index].zobrist_index() {
+            self.hash ^= ZOBRIST_KEYS.key(index, old_piece);
+        }
+        self.board[index] = piece;
+        if let Some(new_piece) = piece.zobrist_index() {
+            self.hash ^= ZOBRIST_KEYS.key(index, new_piece);
         }
-        new_position.board[(m.column+1)*6-1] = m.piece;
-        new_position
     }
 }
 
-#[derive(Copy, Clone, Debug)]
-pub struct ConnectFourMove {
-    pub piece: Piece,
-    pub column: usize
-}
-
-impl Move for ConnectFourMove {
-}
-
-pub struct ConnectFourPositionEvaluator {
-    pub player_piece: Piece
+impl Default for ConnectFourPosition {
+    fn default() -> Self {
+        ConnectFourPosition::new()
+    }
 }
 
-impl ConnectFourPositionEvaluator {
-    fn get_horizontal_winner(&self, position: &ConnectFourPosition, index: usize) -> Piece {
-        if position.board[index] == position.board[index+6]
-            && position.board[index] == position.board[index+12]
-            && position.board[index] == position.board[index+18] {
-            return position.board[index];
+impl ConnectFourPosition {
+    fn get_horizontal_winner(&self, index: usize) -> Piece {
+        if self.board[index] == self.board[index+6]
+            && self.board[index] == self.board[index+12]
+            && self.board[index] == self.board[index+18] {
+            return self.board[index];
         }
 
         Piece::None
     }
 
-    fn get_vertical_winner(&self, position: &ConnectFourPosition, index: usize) -> Piece {
-        if position.board[index] == position.board[index+1]
-            && position.board[index] == position.board[index+2]
-            && position.board[index] == position.board[index+3] {
-            return position.board[index];
+    fn get_vertical_winner(&self, index: usize) -> Piece {
+        if self.board[index] == self.board[index+1]
+            && self.board[index] == self.board[index+2]
+            && self.board[index] == self.board[index+3] {
+            return self.board[index];
         }
 
         Piece::None
     }
 
-    fn get_down_right_winner(&self, position: &ConnectFourPosition, index: usize) -> Piece {
-        if position.board[index] == position.board[index+7]
-            && position.board[index] == position.board[index+14]
-            && position.board[index] == position.board[index+21] {
-            return position.board[index];
+    fn get_down_right_winner(&self, index: usize) -> Piece {
+        if self.board[index] == self.board[index+7]
+            && self.board[index] == self.board[index+14]
+            && self.board[index] == self.board[index+21] {
+            return self.board[index];
         }
 
         Piece::None
     }
 
-    fn get_up_right_winner(&self, position: &ConnectFourPosition, index: usize) -> Piece {
-        if position.board[index] == position.board[index+5]
-            && position.board[index] == position.board[index+10]
-            && position.board[index] == position.board[index+15] {
-            return position.board[index];
+    fn get_up_right_winner(&self, index: usize) -> Piece {
+        if self.board[index] == self.board[index+5]
+            && self.board[index] == self.board[index+10]
+            && self.board[index] == self.board[index+15] {
+            return self.board[index];
         }
 
         Piece::None
     }
-}
 
-impl PositionEvaluator<ConnectFourPosition, ConnectFourMove> for ConnectFourPositionEvaluator {
-    fn evaluate(&self, position: &ConnectFourPosition) -> PositionEvaluation {
+    /// Returns the piece that has four in a row, or Piece::None if nobody has won (yet).
+    pub fn winner(&self) -> Piece {
         // detect horizontal winners
         for col in 0..4 {
             for row in 0..6 {
-                let winner = self.get_horizontal_winner(position, col*6+row);
-                if winner == self.player_piece {
-                    return PositionEvaluation::Winning;
-                } else if winner != Piece::None {
-                    return PositionEvaluation::Losing;
+                let winner = self.get_horizontal_winner(col*6+row);
+                if winner != Piece::None {
+                    return winner;
                 }
             }
         }
@@ -120,11 +120,9 @@ impl PositionEvaluator<ConnectFourPosition, ConnectFourMove> for ConnectFourPosi
         // detect vertical winners
         for col in 0..7 {
             for row in 0..3 {
-                let winner = self.get_vertical_winner(position, col*6+row);
-                if winner == self.player_piece {
-                    return PositionEvaluation::Winning;
-                } else if winner != Piece::None {
-                    return PositionEvaluation::Losing;
+                let winner = self.get_vertical_winner(col*6+row);
+                if winner != Piece::None {
+                    return winner;
                 }
             }
         }
@@ -132,31 +130,220 @@ impl PositionEvaluator<ConnectFourPosition, ConnectFourMove> for ConnectFourPosi
         // detect down_right winners
         for col in 0..4 {
             for row in 0..3 {
-                let winner = self.get_down_right_winner(position, col*6+row);
-                if winner == self.player_piece {
-                    return PositionEvaluation::Winning;
-                } else if winner != Piece::None {
-                    return PositionEvaluation::Losing;
+                let winner = self.get_down_right_winner(col*6+row);
+                if winner != Piece::None {
+                    return winner;
                 }
             }
         }
 
-
         // detect up_right winners
         for col in 0..4 {
-            for row in 3..7 {
-                let winner = self.get_up_right_winner(position, col*6+row);
-                if winner == self.player_piece {
-                    return PositionEvaluation::Winning;
-                } else if winner != Piece::None {
-                    return PositionEvaluation::Losing;
+            for row in 3..6 {
+                let winner = self.get_up_right_winner(col*6+row);
+                if winner != Piece::None {
+                    return winner;
                 }
             }
         }
 
-        PositionEvaluation::Estimate(0.0)
+        Piece::None
+    }
+}
+
+impl Position<ConnectFourPosition, ConnectFourMove> for ConnectFourPosition {
+    #[allow(clippy::clone_on_copy)]
+    fn apply(&self, m: &ConnectFourMove) -> ConnectFourPosition {
+        let mut new_position = self.clone();
+        new_position.make_move(m);
+        new_position
+    }
+
+    fn check_move(&self, m: &ConnectFourMove) -> Result<(), IllegalMoveError> {
+        if self.winner() != Piece::None || self.board.iter().all(|piece| *piece != Piece::None) {
+            return Err(IllegalMoveError::GameOver);
+        }
+        let piece_to_move = match self.last_player {
+            0 => Piece::Black,
+            _ => Piece::Red
+        };
+        if m.piece != piece_to_move {
+            return Err(IllegalMoveError::WrongSideToMove);
+        }
+        if m.column >= 7 {
+            return Err(IllegalMoveError::OffBoard);
+        }
+        // the top cell of a column is the last to fill
+        if self.board[m.column*6] != Piece::None {
+            return Err(IllegalMoveError::ColumnFull(m.column));
+        }
+        Ok(())
+    }
+}
+
+/// Undoing a move needs the player who moved before it, the cell the piece landed in, and whatever was in that cell.
+/// A move into a full column replaces the top piece, as `apply` does, and undoing it puts that piece back.
+impl UnmakePosition<ConnectFourMove> for ConnectFourPosition {
+    type Undo = (usize, usize, Piece);
+
+    fn make_move(&mut self, m: &ConnectFourMove) -> (usize, usize, Piece) {
+        let last_player = self.last_player;
+        self.last_player = match m.piece {
+            Piece::Red => 0,
+            _ => 1
+        };
+
+        // drop the piece into position in the chosen column
+        let index = ((m.column*6)..((m.column+1)*6-1))
+            .find(|i| self.board[i+1] != Piece::None)
+            .unwrap_or((m.column+1)*6-1);
+        let overwritten_piece = self.board[index];
+        self.place(index, m.piece);
+        (last_player, index, overwritten_piece)
+    }
+
+    fn unmake_move(&mut self, _m: &ConnectFourMove, (last_player, index, overwritten_piece): (usize, usize, Piece)) {
+        self.place(index, overwritten_piece);
+        self.last_player = last_player;
+    }
+}
+
+impl ZobristPosition for ConnectFourPosition {
+    fn features(&self) -> Vec<(usize, usize)> {
+        (0..42).filter_map(|index| self.board[index].zobrist_index().map(|piece| (index, piece))).collect()
+    }
+
+    fn zobrist_hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl PositionHash for ConnectFourPosition {
+    fn position_hash(&self) -> u64 {
+        self.hash
     }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ConnectFourMove {
+    pub piece: Piece,
+    pub column: usize
+}
 
+impl Move for ConnectFourMove {
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ConnectFourPositionEvaluator {
+    pub player_piece: Piece
+}
+
+impl ConnectFourPositionEvaluator {
+    /// Returns the piece that has four in a row, or Piece::None if nobody has won (yet).
+    pub fn winner(&self, position: &ConnectFourPosition) -> Piece {
+        position.winner()
+    }
+
+    /// Scores every window of four cells that only one side has played into. The more pieces a side has in such a
+    /// window, the closer it is to completing it, so the weights grow quickly with the piece count.
+    fn estimate(&self, position: &ConnectFourPosition) -> f64 {
+        let other_piece = match self.player_piece {
+            Piece::Red => Piece::Black,
+            _ => Piece::Red
+        };
+        let weight = |count: usize| match count {
+            1 => 1.0,
+            2 => 4.0,
+            3 => 16.0,
+            _ => 0.0
+        };
+
+        let mut score = 0.0;
+        for (start, step) in WINDOWS {
+            let mut own = 0;
+            let mut other = 0;
+            for offset in 0..4 {
+                let piece = position.board[start + offset*step];
+                if piece == self.player_piece {
+                    own += 1;
+                } else if piece == other_piece {
+                    other += 1;
+                }
+            }
+            if other == 0 {
+                score += weight(own);
+            } else if own == 0 {
+                score -= weight(other);
+            }
+        }
+        score
+    }
+}
+
+/// Every window of four cells on the board that could hold a win, as (first cell, distance between cells).
+const WINDOWS: [(usize, usize); 69] = windows();
+
+const fn windows() -> [(usize, usize); 69] {
+    let mut windows = [(0, 0); 69];
+    let mut count = 0;
+    let mut col = 0;
+    while col < 7 {
+        let mut row = 0;
+        while row < 6 {
+            let index = col*6 + row;
+            // horizontal
+            if col < 4 {
+                windows[count] = (index, 6);
+                count += 1;
+            }
+            // vertical
+            if row < 3 {
+                windows[count] = (index, 1);
+                count += 1;
+            }
+            // down_right
+            if col < 4 && row < 3 {
+                windows[count] = (index, 7);
+                count += 1;
+            }
+            // up_right
+            if col < 4 && row >= 3 {
+                windows[count] = (index, 5);
+                count += 1;
+            }
+            row += 1;
+        }
+        col += 1;
+    }
+    windows
+}
+
+impl PositionEvaluator<ConnectFourPosition, ConnectFourMove> for ConnectFourPositionEvaluator {
+    fn evaluate(&self, position: &ConnectFourPosition) -> PositionEvaluation {
+        let winner = position.winner();
+        if winner == self.player_piece {
+            PositionEvaluation::Winning(0)
+        } else if winner != Piece::None {
+            PositionEvaluation::Losing(0)
+        } else if position.board.iter().all(|piece| *piece != Piece::None) {
+            PositionEvaluation::Draw
+        } else {
+            PositionEvaluation::Estimate(self.estimate(position))
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct ConnectFourSideToMovePositionEvaluator {}
+
+impl SideToMovePositionEvaluator<ConnectFourPosition, ConnectFourMove> for ConnectFourSideToMovePositionEvaluator {
+    fn evaluate_for_side_to_move(&self, position: &ConnectFourPosition) -> PositionEvaluation {
+        let player_piece = match position.last_player {
+            0 => Piece::Black,
+            _ => Piece::Red
+        };
+        ConnectFourPositionEvaluator { player_piece }.evaluate(position)
+    }
 }
 
 pub struct ConnectFourMoveGenerator{
@@ -179,6 +366,17 @@ impl MoveGenerator<ConnectFourPosition, ConnectFourMove> for ConnectFourMoveGene
     }
 }
 
+/// Orders moves from the center column outward. Central pieces take part in more potential fours than pieces near
+/// the edges, so central moves are usually stronger.
+pub struct ConnectFourMoveOrderer {
+}
+
+impl MoveOrderer<ConnectFourPosition, ConnectFourMove> for ConnectFourMoveOrderer {
+    fn order_moves(&self, _position: &ConnectFourPosition, moves: &mut [ConnectFourMove], _context: &OrderingContext<ConnectFourMove>) {
+        moves.sort_by_key(|r#move| r#move.column.abs_diff(3));
+    }
+}
+
 pub struct ConnectFourOracle{
     pub position_evaluator: ConnectFourPositionEvaluator
 }
@@ -191,19 +389,223 @@ impl Oracle<ConnectFourPosition, ConnectFourMove> for ConnectFourOracle {
         }
     }
 
-    fn is_terminal(&self, position: &ConnectFourPosition) -> bool {
-        match self.position_evaluator.evaluate(position) {
-            PositionEvaluation::Winning | PositionEvaluation::Losing => return true,
-            _ => ()
-        };
+    fn outcome(&self, position: &ConnectFourPosition) -> Option<GameOutcome> {
+        match self.position_evaluator.winner(position) {
+            Piece::Red => return Some(GameOutcome::Win(0)),
+            Piece::Black => return Some(GameOutcome::Win(1)),
+            _ => {}
+        }
 
         // if nobody's winning, we still need to check for a draw game
         for index in 0..42 {
             if position.board[index] == Piece::None {
-                return false;
+                return None;
             }
         }
 
-        true
+        Some(GameOutcome::Draw)
+    }
+}
+
+/// Connect Four on the standard seven-column, six-row board, with Red (player 0) moving first.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConnectFour {}
+
+impl Game for ConnectFour {
+    type Position = ConnectFourPosition;
+    type Move = ConnectFourMove;
+    type MoveGenerator = ConnectFourMoveGenerator;
+    type Oracle = ConnectFourOracle;
+    type PositionEvaluator = ConnectFourPositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> ConnectFourPosition {
+        ConnectFourPosition::new()
+    }
+
+    fn move_generator(&self) -> ConnectFourMoveGenerator {
+        ConnectFourMoveGenerator {}
+    }
+
+    fn oracle(&self) -> ConnectFourOracle {
+        ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: Piece::Phantom}}
+    }
+
+    fn position_evaluator(&self, player: usize) -> ConnectFourPositionEvaluator {
+        let player_piece = match player {
+            0 => Piece::Red,
+            _ => Piece::Black
+        };
+        ConnectFourPositionEvaluator { player_piece }
+    }
+}
+
+impl DeterministicGame for ConnectFour {}
+
+impl AlternatingGame for ConnectFour {
+    type SideToMovePositionEvaluator = ConnectFourSideToMovePositionEvaluator;
+
+    fn side_to_move_position_evaluator(&self) -> ConnectFourSideToMovePositionEvaluator {
+        ConnectFourSideToMovePositionEvaluator {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connect_four::{ConnectFourMove, ConnectFourOracle, ConnectFourPosition, ConnectFourPositionEvaluator, Piece, ZOBRIST_KEYS};
+    use crate::core::game::{GameOutcome, IllegalMoveError, Oracle, Position, UnmakePosition};
+    use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+    use crate::core::zobrist::ZobristPosition;
+
+    #[test]
+    pub fn test_apply_updates_hash() {
+        let position = ConnectFourPosition::new();
+        assert_eq!(position.zobrist_hash(), 0);
+
+        let red_left = position.apply(&ConnectFourMove { piece: Piece::Red, column: 0 })
+            .apply(&ConnectFourMove { piece: Piece::Black, column: 3 })
+            .apply(&ConnectFourMove { piece: Piece::Red, column: 6 });
+        let red_right = position.apply(&ConnectFourMove { piece: Piece::Red, column: 6 })
+            .apply(&ConnectFourMove { piece: Piece::Black, column: 3 })
+            .apply(&ConnectFourMove { piece: Piece::Red, column: 0 });
+        assert_eq!(red_left.zobrist_hash(), red_right.zobrist_hash());
+        assert_eq!(red_left.zobrist_hash(), ZOBRIST_KEYS.hash(red_left.features()));
+
+        // same columns, but the pieces stack in a different order
+        let stacked = position.apply(&ConnectFourMove { piece: Piece::Red, column: 3 })
+            .apply(&ConnectFourMove { piece: Piece::Black, column: 3 });
+        let swapped = position.apply(&ConnectFourMove { piece: Piece::Black, column: 3 })
+            .apply(&ConnectFourMove { piece: Piece::Red, column: 3 });
+        assert_ne!(stacked.zobrist_hash(), swapped.zobrist_hash());
+        assert_eq!(stacked.zobrist_hash(), ZOBRIST_KEYS.hash(stacked.features()));
+    }
+
+    #[test]
+    pub fn test_make_and_unmake_move() {
+        let moves = [(Piece::Red, 3), (Piece::Black, 3), (Piece::Red, 0), (Piece::Black, 6), (Piece::Red, 3)];
+        let mut position = ConnectFourPosition::new();
+        let mut positions = vec![position];
+        let mut undos = vec![];
+        for (piece, column) in moves {
+            let r#move = ConnectFourMove { piece, column };
+            let applied = position.apply(&r#move);
+            undos.push(position.make_move(&r#move));
+            assert_eq!(position.board, applied.board);
+            assert_eq!(position.last_player, applied.last_player);
+            assert_eq!(position.zobrist_hash(), applied.zobrist_hash());
+            positions.push(position);
+        }
+
+        // taking the moves back in reverse passes through every position again
+        for ((piece, column), undo) in moves.into_iter().zip(undos).rev() {
+            positions.pop();
+            position.unmake_move(&ConnectFourMove { piece, column }, undo);
+            let before = positions.last().unwrap();
+            assert_eq!(position.board, before.board);
+            assert_eq!(position.last_player, before.last_player);
+            assert_eq!(position.zobrist_hash(), before.zobrist_hash());
+        }
+    }
+
+    #[test]
+    pub fn test_unmake_move_into_a_full_column() {
+        let mut position = ConnectFourPosition::new();
+        for piece in [Piece::Red, Piece::Black, Piece::Red, Piece::Black, Piece::Red, Piece::Black] {
+            position = position.apply(&ConnectFourMove { piece, column: 0 });
+        }
+        let full = position;
+
+        // the move replaces the top piece, and taking it back restores it
+        let r#move = ConnectFourMove { piece: Piece::Red, column: 0 };
+        let undo = position.make_move(&r#move);
+        assert_eq!(position.board, full.apply(&r#move).board);
+        position.unmake_move(&r#move, undo);
+        assert_eq!(position.board, full.board);
+        assert_eq!(position.last_player, full.last_player);
+        assert_eq!(position.zobrist_hash(), full.zobrist_hash());
+    }
+
+    #[test]
+    pub fn test_check_move() {
+        let red = |column: usize| ConnectFourMove { piece: Piece::Red, column };
+        let black = |column: usize| ConnectFourMove { piece: Piece::Black, column };
+
+        let mut position = ConnectFourPosition::new();
+        assert_eq!(position.check_move(&black(0)), Err(IllegalMoveError::WrongSideToMove));
+        assert_eq!(position.check_move(&red(7)), Err(IllegalMoveError::OffBoard));
+        for _ in 0..3 {
+            position = position.try_apply(&red(0)).unwrap().try_apply(&black(0)).unwrap();
+        }
+        assert_eq!(position.check_move(&red(0)), Err(IllegalMoveError::ColumnFull(0)));
+        assert_eq!(position.check_move(&red(1)), Ok(()));
+
+        for _ in 0..3 {
+            position = position.apply(&red(1)).apply(&black(2));
+        }
+        position = position.apply(&red(1));
+        assert_eq!(position.check_move(&black(2)), Err(IllegalMoveError::GameOver));
+    }
+
+    #[test]
+    pub fn test_winner() {
+        let evaluator = ConnectFourPositionEvaluator { player_piece: Piece::Red };
+        let mut position = ConnectFourPosition::new();
+        assert_eq!(position.winner(), Piece::None);
+
+        // an up_right diagonal from the bottom of column 0 to the fourth row of column 3
+        for index in [5, 10, 15, 20] {
+            position.place(index, Piece::Red);
+        }
+        assert_eq!(position.winner(), Piece::Red);
+        assert_eq!(evaluator.evaluate(&position), PositionEvaluation::Winning(0));
+
+        // four cells that only line up if the up_right search runs off the bottom of a column
+        let mut position = ConnectFourPosition::new();
+        for index in [6, 11, 16, 21] {
+            position.place(index, Piece::Black);
+        }
+        assert_eq!(position.winner(), Piece::None);
+    }
+
+    #[test]
+    pub fn test_outcome() {
+        let oracle = ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: Piece::Phantom}};
+        let mut position = ConnectFourPosition::new();
+        assert_eq!(oracle.outcome(&position), None);
+
+        for index in [5, 11, 17, 23] {
+            position.place(index, Piece::Black);
+        }
+        assert_eq!(oracle.outcome(&position), Some(GameOutcome::Win(1)));
+        assert!(oracle.is_terminal(&position));
+
+        // alternate pairs of rows in every column, so no four of the same piece line up in any direction
+        let mut position = ConnectFourPosition::new();
+        for col in 0..7 {
+            for row in 0..6 {
+                position.place(col*6+row, match (row / 2 + col) % 2 {
+                    0 => Piece::Red,
+                    _ => Piece::Black
+                });
+            }
+        }
+        assert_eq!(position.winner(), Piece::None);
+        assert_eq!(oracle.outcome(&position), Some(GameOutcome::Draw));
+    }
+
+    #[test]
+    pub fn test_estimate() {
+        let red_evaluator = ConnectFourPositionEvaluator { player_piece: Piece::Red };
+        let black_evaluator = ConnectFourPositionEvaluator { player_piece: Piece::Black };
+        let mut position = ConnectFourPosition::new();
+        assert_eq!(red_evaluator.evaluate(&position), PositionEvaluation::Estimate(0.0));
+
+        // red in the bottom of the center column
+        position.place(23, Piece::Red);
+        assert!(red_evaluator.evaluate(&position) > PositionEvaluation::Estimate(0.0));
+        assert!(black_evaluator.evaluate(&position) < PositionEvaluation::Estimate(0.0));
     }
 }
@@ -1,53 +1,454 @@
-use std::fmt::Debug;
-use crate::core::game::{Position, Oracle};
-use crate::core::player::Player;
-use crate::core::r#move::Move;
-
-pub struct GameEngine<PositionType, MoveType, PlayerType, OracleType>
-    where PositionType: Position<PositionType, MoveType> + Debug,
-          MoveType: Move,
-          PlayerType: Player<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::{Duration, Instant};
+use crate::core::game::{Game, IllegalMoveError, Position, Oracle};
+use crate::core::observation::{ImperfectInformationGame, ObservationFor};
+use crate::core::observer::GameObserver;
+use crate::core::player::{ObservedPlayer, ObservingPlayer, Player};
+use crate::core::position_evaluator::PositionEvaluation;
+use crate::core::random::Random;
+use crate::core::r#move::MoveGenerator;
+use crate::core::record::{GameRecord, GameRecordFor, RecordedMove};
+
+/// Why the engine stopped a game before it was over.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameEngineError {
+    /// The game needs a different number of players than the engine was given.
+    WrongNumberOfPlayers { expected: usize, actual: usize },
+    /// The oracle said it was the turn of a player the engine doesn't have.
+    NoSuchPlayer(usize),
+    /// A move was submitted when it wasn't any player's turn, because the game is over or waiting on a chance event.
+    NoPlayerToMove,
+    /// The player with the given index had no move to play, not even a pass, though the game wasn't over.
+    NoMoves(usize),
+    /// The player with the given index picked a move that isn't legal.
+    IllegalMove { player: usize, error: IllegalMoveError }
+}
+
+impl Display for GameEngineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameEngineError::WrongNumberOfPlayers { expected, actual } => write!(f, "the game needs {} players, but {} were given", expected, actual),
+            GameEngineError::NoSuchPlayer(player) => write!(f, "there is no player {}", player),
+            GameEngineError::NoPlayerToMove => write!(f, "it isn't any player's turn"),
+            GameEngineError::NoMoves(player) => write!(f, "player {} has no moves, but the game isn't over", player),
+            GameEngineError::IllegalMove { player, error } => write!(f, "player {} picked an illegal move: {}", player, error)
+        }
+    }
+}
+
+impl Error for GameEngineError {}
+
+pub struct GameEngine<GameType>
+    where GameType: Game
 {
-    initial_game_state: PositionType,
-    moves: Vec<MoveType>,
-    current_game_state: PositionType,
+    initial_game_state: GameType::Position,
+    moves: Vec<RecordedMove<GameType::Move>>,
+    current_game_state: GameType::Position,
     // TODO: We maybe don't want to own these.
-    player_0: PlayerType,
-    player_1: PlayerType,
-    oracle: OracleType
+    players: Vec<Box<dyn Player<GameType::Position, GameType::Move>>>,
+    player_count: usize,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    observers: Vec<Box<dyn GameObserver<GameType::Position, GameType::Move>>>,
+    random: Random,
+    started: bool,
+    finished: bool
 }
 
-impl<PositionType, MoveType, PlayerType, OracleType> GameEngine<PositionType, MoveType, PlayerType, OracleType>
-    where PositionType: Position<PositionType, MoveType> + Debug,
-          MoveType: Move,
-          PlayerType: Player<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+impl<GameType> GameEngine<GameType>
+    where GameType: Game
 {
-    pub fn new(initial_game_state: PositionType, player1: PlayerType, player2: PlayerType, oracle: OracleType) -> GameEngine<PositionType, MoveType, PlayerType, OracleType> {
+    /// Set up a game between the given players, starting from the game's initial position. The players are indexed
+    /// in the order given, so the first one is player 0.
+    pub fn new(game: &GameType, players: Vec<Box<dyn Player<GameType::Position, GameType::Move>>>) -> GameEngine<GameType> {
+        let initial_game_state = game.initial_position();
         GameEngine {
             initial_game_state,
             moves: vec![],
             current_game_state: initial_game_state,
-            player_0: player1,
-            player_1: player2,
-            oracle
+            players,
+            player_count: game.player_count(),
+            move_generator: game.move_generator(),
+            oracle: game.oracle(),
+            observers: vec![],
+            random: Random::new(0),
+            started: false,
+            finished: false
+        }
+    }
+
+    /// Set up a game with hidden information between the given players, starting from the game's initial position.
+    /// Each player is only shown its own observation of each position, made with the game's observation model.
+    pub fn for_observing_players(game: &GameType, players: Vec<Box<dyn ObservingPlayer<ObservationFor<GameType>, GameType::Move>>>) -> GameEngine<GameType>
+        where GameType: ImperfectInformationGame,
+              GameType::Position: 'static,
+              GameType::Move: 'static,
+              GameType::ObservationModel: 'static {
+        let players = players.into_iter()
+            .enumerate()
+            .map(|(player_index, player)| Box::new(ObservedPlayer::new(player_index, game.observation_model(), player)) as Box<dyn Player<GameType::Position, GameType::Move>>)
+            .collect();
+        GameEngine::new(game, players)
+    }
+
+    /// Tell the observer about everything that happens in the game, along with any observers already added. The
+    /// engine prints nothing itself: add a {@link ConsolePrinter} to see the game as it goes.
+    pub fn with_observer(mut self, observer: Box<dyn GameObserver<GameType::Position, GameType::Move>>) -> GameEngine<GameType> {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Start the game from the given position instead of the game's initial position, like an opening from a book.
+    pub fn with_initial_position(mut self, position: GameType::Position) -> GameEngine<GameType> {
+        self.initial_game_state = position;
+        self.current_game_state = position;
+        self
+    }
+
+    /// Seed the generator that picks which chance event happens at each chance node. Defaults to 0, and games with
+    /// the same seed and players always play out the same way.
+    pub fn with_seed(mut self, seed: u64) -> GameEngine<GameType> {
+        self.random = Random::new(seed);
+        self
+    }
+
+    /// The position the game has reached.
+    pub fn position(&self) -> GameType::Position {
+        self.current_game_state
+    }
+
+    /// The index of the player whose turn it is, or None if the game is over, or if it's waiting on a chance event
+    /// rather than a player.
+    pub fn player_to_move(&self) -> Option<usize> {
+        if self.is_over() || !self.move_generator.get_chance_events(&self.current_game_state).is_empty() {
+            return None;
+        }
+        self.oracle.next_player(&self.current_game_state)
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.oracle.is_terminal(&self.current_game_state)
+    }
+
+    /// The record of the game so far. Its outcome is None until the game is over.
+    pub fn record(&self) -> GameRecordFor<GameType> {
+        GameRecord {
+            initial_position: self.initial_game_state,
+            moves: self.moves.clone(),
+            outcome: self.oracle.outcome(&self.current_game_state)
+        }
+    }
+
+    /// Advance the game by a single ply, and return the move played, or None if no move can be played, because the
+    /// game is over or the oracle can't say whose turn it is. At a chance node, the engine plays one of the chance
+    /// events, picked at random with its probability. When the oracle says the player to move must pass, the engine
+    /// passes for them. Otherwise the player to move picks the move, and a player who has no move passes, if the game
+    /// lets them.
+    ///
+    /// Returns an error if the engine doesn't have the players the game needs, if the player to move has no move
+    /// though the game isn't over, or if they picked an illegal move, in which case the move isn't played.
+    pub fn step(&mut self) -> Result<Option<RecordedMove<GameType::Move>>, GameEngineError> {
+        if self.players.len() != self.player_count {
+            return Err(GameEngineError::WrongNumberOfPlayers { expected: self.player_count, actual: self.players.len() });
+        }
+        self.start();
+        if self.is_over() {
+            return Ok(None);
         }
+
+        let chance_events = self.move_generator.get_chance_events(&self.current_game_state);
+        if !chance_events.is_empty() {
+            let chance_event = self.random.pick_weighted(&chance_events);
+            return self.play(chance_event, None, Duration::ZERO, None).map(Some);
+        }
+
+        let player_index = match self.oracle.next_player(&self.current_game_state) {
+            Some(player_index) => player_index,
+            None => return Ok(None)
+        };
+        let next_player = self.players.get(player_index).ok_or(GameEngineError::NoSuchPlayer(player_index))?;
+
+        // a player who must pass has nothing to decide
+        if self.oracle.must_pass(&self.current_game_state) {
+            if let Some(pass) = self.move_generator.get_pass(&self.current_game_state) {
+                return self.play(pass, Some(player_index), Duration::ZERO, None).map(Some);
+            }
+        }
+
+        // a player without a move passes, if the game allows it
+        let start = Instant::now();
+        let (next_move, evaluation) = next_player.pick_evaluated_move(&self.current_game_state)
+            .or_else(|| self.move_generator.get_pass(&self.current_game_state).map(|pass| (pass, None)))
+            .ok_or(GameEngineError::NoMoves(player_index))?;
+        self.play(next_move, Some(player_index), start.elapsed(), evaluation).map(Some)
     }
 
-    pub fn run(&mut self) {
-        while !self.oracle.is_terminal(&self.current_game_state) {
+    /// Play a move chosen outside the engine, like a move a user picked, for the player to move, and return it as
+    /// recorded. Returns an error if no player is to move, or if the move is illegal, in which case it isn't played.
+    pub fn submit_move(&mut self, r#move: GameType::Move) -> Result<RecordedMove<GameType::Move>, GameEngineError> {
+        let player_index = self.player_to_move().ok_or(GameEngineError::NoPlayerToMove)?;
+        self.start();
+        self.play(r#move, Some(player_index), Duration::ZERO, None)
+    }
+
+    /// Play the game out, one {@link step} at a time, and return its record, which says how it ended. The outcome is
+    /// None if the oracle couldn't say whose turn it was before the game ended. Returns the first error a step
+    /// returns.
+    pub fn run(&mut self) -> Result<GameRecordFor<GameType>, GameEngineError> {
+        while self.step()?.is_some() {}
+        Ok(self.finish())
+    }
+
+    /// Tell the observers the game has started, unless they've already been told.
+    fn start(&mut self) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        for observer in &mut self.observers {
+            observer.game_started(&self.current_game_state);
+        }
+    }
 
-            let next_player = match self.oracle.next_player(&self.current_game_state) {
-                Some(0) => &self.player_0,
-                Some(1) => &self.player_1,
-                _ => return
-            };
+    /// Play the move, if it's legal, add it to the record along with the player's evaluation of it, and tell the
+    /// observers about it. The player is None for a chance event, and the observers are told when the move ends the
+    /// game.
+    fn play(&mut self, r#move: GameType::Move, player: Option<usize>, time_taken: Duration, evaluation: Option<PositionEvaluation>) -> Result<RecordedMove<GameType::Move>, GameEngineError> {
+        self.current_game_state = match player {
+            Some(player_index) => self.current_game_state.try_apply(&r#move)
+                .map_err(|error| GameEngineError::IllegalMove { player: player_index, error })?,
+            None => self.current_game_state.apply(&r#move)
+        };
+        let recorded_move = RecordedMove { r#move, player, time_taken, evaluation };
+        for observer in &mut self.observers {
+            observer.move_played(&recorded_move);
+            observer.position_changed(&self.current_game_state);
+        }
+        self.moves.push(recorded_move);
+        if self.is_over() {
+            self.finish();
+        }
+        Ok(recorded_move)
+    }
 
-            let next_move = next_player.pick_move(&self.current_game_state);
-            self.current_game_state = self.current_game_state.apply(&next_move);
-            println!("{:?}", self.current_game_state);
-            self.moves.push(next_move);
+    /// The record of the game, which the observers are told is over, unless they've already been told.
+    fn finish(&mut self) -> GameRecordFor<GameType> {
+        let record = self.record();
+        if !self.finished {
+            self.finished = true;
+            for observer in &mut self.observers {
+                observer.game_over(&record);
+            }
         }
+        record
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use crate::core::engine::{GameEngine, GameEngineError};
+    use crate::core::expectimax::ExpectimaxMoveStrategy;
+    use crate::core::game::{GameOutcome, IllegalMoveError, Position};
+    use crate::core::r#move::Move;
+    use crate::core::move_strategy::NegamaxMoveStrategy;
+    use crate::core::observer::{GameEvent, RecordingObserver};
+    use crate::core::player::{DefaultPlayer, ObservingPlayer, Player};
+    use crate::core::position_evaluator::PositionEvaluation;
+    use crate::core::record::RecordedMove;
+    use crate::core::test_support::{max_n_player, negamax_player, Countdown};
+    use crate::othello::{Othello, OthelloMove, OthelloPosition, Piece as OthelloPiece};
+    use crate::goofspiel::{Goofspiel, GoofspielMove, GoofspielObservation};
+    use crate::pig::{Pig, PigMove};
+    use crate::tictactoe::{Piece, TicTacToe, TicTacToeMove, TicTacToePosition};
+
+    #[test]
+    pub fn test_run_returns_the_outcome() {
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)]);
+        assert_eq!(engine.run().map(|record| record.outcome), Ok(Some(GameOutcome::Draw)));
+    }
+
+    #[test]
+    pub fn test_run_records_the_game() {
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)]);
+        let record = engine.run().unwrap();
+        assert_eq!(record.moves.len(), 9);
+        assert_eq!(record.moves[0].player, Some(0));
+        assert_eq!(record.moves[1].player, Some(1));
+        // both players search to the end of the game, so each of them knows every move it plays leads to a draw
+        assert!(record.moves.iter().all(|recorded_move| recorded_move.evaluation == Some(PositionEvaluation::Draw)));
+        assert_eq!(record.final_position().board, engine.current_game_state.board);
+
+        let mut replay = record.replay();
+        replay.go_to(9);
+        assert_eq!(replay.position().board, engine.current_game_state.board);
+    }
+
+    #[test]
+    pub fn test_step_and_submit_move() {
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)]);
+        assert_eq!(engine.player_to_move(), Some(0));
+
+        // the user plays X in a corner, and the engine answers for O
+        let x_move = TicTacToeMove { position: 0, piece: Piece::X };
+        assert_eq!(engine.submit_move(x_move).map(|recorded_move| (recorded_move.r#move, recorded_move.evaluation)), Ok((x_move, None)));
+        assert_eq!(engine.player_to_move(), Some(1));
+        let o_move = engine.step().unwrap().unwrap();
+        assert_eq!(o_move.player, Some(1));
+        assert_eq!(engine.position().board[o_move.r#move.position], Some(Piece::O));
+
+        // a move that isn't legal is refused, and the position stays as it was
+        assert_eq!(engine.submit_move(x_move), Err(GameEngineError::IllegalMove { player: 0, error: IllegalMoveError::SquareOccupied(0) }));
+        assert_eq!(engine.record().moves.len(), 2);
+
+        let record = engine.run().unwrap();
+        assert!(engine.is_over());
+        assert_eq!(engine.player_to_move(), None);
+        assert_eq!(record.outcome, Some(GameOutcome::Draw));
+        assert_eq!(engine.step(), Ok(None));
+        assert_eq!(engine.submit_move(x_move), Err(GameEngineError::NoPlayerToMove));
+    }
+
+    #[test]
+    pub fn test_no_player_to_move_at_chance_nodes() {
+        let game = Pig { target: 20 };
+        let player = |player| Box::new(DefaultPlayer::new(Box::new(ExpectimaxMoveStrategy::for_game(&game, player).with_max_depth(3))));
+        let mut engine = GameEngine::new(&game, vec![player(0), player(1)]);
+        engine.submit_move(PigMove::Roll).unwrap();
+        assert_eq!(engine.player_to_move(), None);
+        assert_eq!(engine.submit_move(PigMove::Hold), Err(GameEngineError::NoPlayerToMove));
+        assert!(matches!(engine.step(), Ok(Some(RecordedMove { r#move: PigMove::Die(_), player: None, .. }))));
+    }
+
+    #[test]
+    pub fn test_run_tells_every_observer() {
+        let observers = [Rc::new(RefCell::new(RecordingObserver::new())), Rc::new(RefCell::new(RecordingObserver::new()))];
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)])
+            .with_observer(Box::new(observers[0].clone()))
+            .with_observer(Box::new(observers[1].clone()));
+        engine.run().unwrap();
+
+        for observer in observers {
+            let events = &observer.borrow().events;
+            // the start, a move and a position for each of the nine moves, and the end
+            assert_eq!(events.len(), 20);
+            assert!(matches!(events[0], GameEvent::GameStarted(_)));
+            assert!(matches!(events[1], GameEvent::MovePlayed(RecordedMove { player: Some(0), .. })));
+            assert!(matches!(events[2], GameEvent::PositionChanged(_)));
+            assert!(matches!(events[19], GameEvent::GameOver(Some(GameOutcome::Draw))));
+        }
+    }
+
+    /// Always plays the same move, whether or not it's legal.
+    struct StubbornPlayer<MoveType> {
+        r#move: MoveType
+    }
+
+    impl<PositionType, MoveType> Player<PositionType, MoveType> for StubbornPlayer<MoveType>
+        where PositionType: Position<PositionType, MoveType>,
+              MoveType: Move
+    {
+        fn pick_move(&self, _game: &PositionType) -> Option<MoveType> {
+            Some(self.r#move)
+        }
+    }
+
+    /// Never has a move to play.
+    struct ResigningPlayer {}
+
+    impl Player<TicTacToePosition, TicTacToeMove> for ResigningPlayer {
+        fn pick_move(&self, _game: &TicTacToePosition) -> Option<TicTacToeMove> {
+            None
+        }
+    }
+
+    #[test]
+    pub fn test_run_reports_a_player_without_moves() {
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![Box::new(ResigningPlayer {}), Box::new(ResigningPlayer {})]);
+        assert_eq!(engine.run().err(), Some(GameEngineError::NoMoves(0)));
+    }
+
+    #[test]
+    pub fn test_run_plays_through_passes() {
+        let player = || Box::new(DefaultPlayer::new(Box::new(NegamaxMoveStrategy::for_game(&Othello {}).with_max_depth(2))));
+        let mut engine = GameEngine::new(&Othello {}, vec![player(), player()]);
+        let record = engine.run().unwrap();
+        assert!(record.outcome.is_some());
+        assert!(record.moves.iter().any(|recorded_move| recorded_move.r#move == OthelloMove::Pass));
+    }
+
+    #[test]
+    pub fn test_run_passes_for_a_player_who_must() {
+        // White in the corner, and Black next to it, with Black to move: Black can't capture, and must pass
+        let mut board = [None; 64];
+        board[0] = Some(OthelloPiece::White);
+        board[1] = Some(OthelloPiece::Black);
+        // both players would place on the square beside Black, which is only legal for White
+        let player = || Box::new(StubbornPlayer { r#move: OthelloMove::Place(2) });
+        let mut engine = GameEngine::new(&Othello {}, vec![player(), player()])
+            .with_initial_position(OthelloPosition::from_board(board, 1));
+        let record = engine.run().unwrap();
+        assert_eq!(record.moves.iter().map(|recorded_move| recorded_move.r#move).collect::<Vec<_>>(), vec![OthelloMove::Pass, OthelloMove::Place(2)]);
+        assert_eq!(record.outcome, Some(GameOutcome::Win(1)));
+    }
+
+    #[test]
+    pub fn test_run_refuses_illegal_moves() {
+        let player_0 = Box::new(StubbornPlayer { r#move: TicTacToeMove { position: 4, piece: Piece::X } });
+        let player_1 = Box::new(StubbornPlayer { r#move: TicTacToeMove { position: 4, piece: Piece::O } });
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![player_0, player_1]);
+        assert_eq!(engine.run().err(), Some(GameEngineError::IllegalMove { player: 1, error: IllegalMoveError::SquareOccupied(4) }));
+        assert_eq!(engine.current_game_state.board[4], Some(Piece::X));
+    }
+
+    #[test]
+    pub fn test_run_with_three_players() {
+        let game = Countdown { counters: 5 };
+        let mut engine = GameEngine::new(&game, vec![max_n_player(&game, 0), max_n_player(&game, 1), max_n_player(&game, 2)]);
+        assert_eq!(engine.run().map(|record| record.outcome), Ok(Some(GameOutcome::Win(0))));
+    }
+
+    #[test]
+    pub fn test_run_needs_every_player() {
+        let game = Countdown { counters: 5 };
+        let mut engine = GameEngine::new(&game, vec![max_n_player(&game, 0), max_n_player(&game, 1)]);
+        assert_eq!(engine.run().err(), Some(GameEngineError::WrongNumberOfPlayers { expected: 3, actual: 2 }));
+    }
+
+    #[test]
+    pub fn test_run_rolls_the_dice_by_seed() {
+        let game = Pig { target: 20 };
+        let play = |seed: u64| {
+            let player = |player| Box::new(DefaultPlayer::new(Box::new(ExpectimaxMoveStrategy::for_game(&game, player).with_max_depth(3))));
+            let mut engine = GameEngine::new(&game, vec![player(0), player(1)]).with_seed(seed);
+            let record = engine.run().unwrap();
+            assert!(matches!(record.outcome, Some(GameOutcome::Win(_))));
+            record.moves.iter().map(|recorded_move| recorded_move.r#move).collect::<Vec<_>>()
+        };
+
+        let moves = play(7);
+        assert!(moves.iter().any(|r#move| matches!(r#move, PigMove::Die(_))));
+        assert_eq!(play(7), moves);
+        assert_ne!(play(8), moves);
+    }
+
+    /// Bids its highest card, and checks that it can't see the other player's bid.
+    struct HighestCardPlayer {}
+
+    impl ObservingPlayer<GoofspielObservation, GoofspielMove> for HighestCardPlayer {
+        fn pick_move(&self, observation: &GoofspielObservation) -> Option<GoofspielMove> {
+            assert_eq!(observation.position.bid, None);
+            let hand = observation.position.hand(observation.player);
+            hand.last().map(|&card| GoofspielMove { player: observation.player, card })
+        }
+    }
+
+    #[test]
+    pub fn test_run_shows_players_only_their_observations() {
+        let game = Goofspiel::new(5).unwrap();
+        let mut engine = GameEngine::for_observing_players(&game, vec![Box::new(HighestCardPlayer {}), Box::new(HighestCardPlayer {})]);
+        assert_eq!(engine.run().map(|record| record.outcome), Ok(Some(GameOutcome::Draw)));
+    }
+}
@@ -0,0 +1,190 @@
+use crate::core::game::{Game, Position, Oracle};
+use crate::core::move_strategy::MoveStrategy;
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use crate::core::r#move::MoveGenerator;
+
+/// How many plies deep the search goes unless it's given a depth. Games with chance events can go on forever, like
+/// Pig, where a roll of 1 passes the turn, so the search needs a limit.
+pub const DEFAULT_MAX_DEPTH: usize = 8;
+
+/// An ExpectimaxMoveStrategy extends minimax to games with chance events, like dice rolls.
+///
+/// The player to move at the root maximizes, and every other player minimizes, as in minimax. Turns needn't
+/// alternate: the oracle says whose turn it is at every node. At chance nodes, the search can't know which event will
+/// happen, so it takes the average of the events' evaluations, weighted by their probabilities.
+///
+/// Averaging only makes sense for numbers, so at chance nodes a win counts as 1, a loss as -1 and a draw as 0. The
+/// position evaluator's estimates should lie between -1 and 1 to be weighed fairly against them. A chance node is
+/// only proven won, lost or drawn when every one of its events is, and then it takes the worst of them.
+pub struct ExpectimaxMoveStrategy<GameType>
+    where GameType: Game
+{
+    position_evaluator: GameType::PositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize
+}
+
+impl<GameType> ExpectimaxMoveStrategy<GameType>
+    where GameType: Game
+{
+    pub fn new(position_evaluator: GameType::PositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> ExpectimaxMoveStrategy<GameType> {
+        ExpectimaxMoveStrategy {
+            position_evaluator,
+            move_generator,
+            oracle,
+            max_depth: DEFAULT_MAX_DEPTH
+        }
+    }
+
+    /// Create a strategy for the given game that plays for the player with the given index.
+    pub fn for_game(game: &GameType, player: usize) -> ExpectimaxMoveStrategy<GameType> {
+        ExpectimaxMoveStrategy::new(game.position_evaluator(player), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies, counting chance events as plies, instead of {@link DEFAULT_MAX_DEPTH}.
+    /// Positions at that depth are scored with the position evaluator's estimate instead of being searched further.
+    /// The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> ExpectimaxMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The evaluation of the position, for the player choosing the move at the root.
+    fn evaluate(&self, game: &GameType::Position, depth: usize, root_player: usize) -> PositionEvaluation {
+        if depth == 0 || self.oracle.is_terminal(game) {
+            return self.position_evaluator.evaluate(game);
+        }
+
+        let chance_events = self.move_generator.get_chance_events(game);
+        if !chance_events.is_empty() {
+            let position_evaluations: Vec<(PositionEvaluation, f64)> = chance_events.iter()
+                .map(|(r#move, probability)| (self.evaluate(&game.apply(r#move), depth - 1, root_player).add_ply(), *probability))
+                .collect();
+            return expected_evaluation(&position_evaluations);
+        }
+
+        match self.oracle.next_player(game) {
+            Some(player) => match self.choose_move_recursive(game, depth, player == root_player, root_player) {
+                Some((_, position_evaluation)) => position_evaluation,
+                None => self.position_evaluator.evaluate(game)
+            },
+            None => self.position_evaluator.evaluate(game)
+        }
+    }
+
+    /// Pick the best of the moves in a position where a player is to move: the best for the root player if
+    /// `maximizing_player` is true, and the worst for them otherwise. Returns None if there are no moves.
+    fn choose_move_recursive(&self, game: &GameType::Position, depth: usize, maximizing_player: bool, root_player: usize) -> Option<(GameType::Move, PositionEvaluation)> {
+        let mut best: Option<(GameType::Move, PositionEvaluation)> = None;
+        for r#move in self.move_generator.get_moves_or_pass(game) {
+            let new_position_evaluation = self.evaluate(&game.apply(&r#move), depth - 1, root_player).add_ply();
+            let is_better = match best {
+                None => true,
+                Some((_, best_position_evaluation)) if maximizing_player => new_position_evaluation.improves_on(&best_position_evaluation),
+                Some((_, best_position_evaluation)) => (-new_position_evaluation).improves_on(&-best_position_evaluation)
+            };
+            if is_better {
+                best = Some((r#move, new_position_evaluation));
+            }
+        }
+        best
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for ExpectimaxMoveStrategy<GameType>
+    where GameType: Game {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        if self.oracle.is_terminal(game) {
+            return None;
+        }
+        let root_player = self.oracle.next_player(game)?;
+        self.choose_move_recursive(game, self.max_depth.max(1), true, root_player).map(|(best_move, evaluation)| (best_move, Some(evaluation)))
+    }
+}
+
+/// The evaluation of a chance node, given the evaluations of its events and their probabilities.
+fn expected_evaluation(position_evaluations: &[(PositionEvaluation, f64)]) -> PositionEvaluation {
+    let all_proven = |is_proven: fn(&PositionEvaluation) -> bool| position_evaluations.iter().all(|(position_evaluation, _)| is_proven(position_evaluation));
+    if all_proven(|e| matches!(e, PositionEvaluation::Winning(_)))
+        || all_proven(|e| matches!(e, PositionEvaluation::Losing(_)))
+        || all_proven(|e| matches!(e, PositionEvaluation::Draw)) {
+        let mut worst = position_evaluations[0].0;
+        for &(position_evaluation, _) in &position_evaluations[1..] {
+            if position_evaluation < worst {
+                worst = position_evaluation;
+            }
+        }
+        return worst;
+    }
+
+    let expected_value = position_evaluations.iter()
+        .map(|&(position_evaluation, probability)| probability * match position_evaluation {
+            PositionEvaluation::Winning(_) => 1.0,
+            PositionEvaluation::Losing(_) => -1.0,
+            PositionEvaluation::Draw => 0.0,
+            PositionEvaluation::Estimate(estimate) => estimate
+        })
+        .sum();
+    PositionEvaluation::Estimate(expected_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::expectimax::ExpectimaxMoveStrategy;
+    use crate::core::game::{Oracle, Position};
+    use crate::core::move_strategy::{MinimaxMoveStrategy, MoveStrategy};
+    use crate::core::r#move::MoveGenerator;
+    use crate::pig::{Pig, PigMove, PigPosition};
+    use crate::tictactoe::{TicTacToe, TicTacToeMoveGenerator, TicTacToeOracle, TicTacToePosition};
+
+    #[test]
+    pub fn test_expectimax_matches_minimax_without_chance() {
+        let minimax = MinimaxMoveStrategy::for_game(&TicTacToe {}, 0);
+        let expectimax = ExpectimaxMoveStrategy::for_game(&TicTacToe {}, 0);
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(minimax.choose_move(&game).unwrap().position, expectimax.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_expectimax_weighs_the_odds() {
+        let expectimax = ExpectimaxMoveStrategy::for_game(&Pig { target: 20 }, 0)
+            .with_max_depth(4);
+
+        // holding on nothing gains nothing, and a roll gains points five times in six
+        let game = PigPosition::new(20);
+        assert_eq!(expectimax.choose_move(&game), Some(PigMove::Roll));
+
+        // holding wins outright, while rolling risks a 1
+        let game = PigPosition { scores: [15, 0], turn_total: 5, ..PigPosition::new(20) };
+        assert_eq!(expectimax.choose_move(&game), Some(PigMove::Hold));
+
+        // with this much at stake, a 1 would lose much more than any other face could gain
+        let game = PigPosition { turn_total: 45, ..PigPosition::new(100) };
+        assert_eq!(expectimax.choose_move(&game), Some(PigMove::Hold));
+    }
+
+    #[test]
+    pub fn test_default_depth_plays_a_game_without_an_end() {
+        // Pig can go on forever, so a search without a depth limit would never return
+        let expectimax = ExpectimaxMoveStrategy::for_game(&Pig { target: 100 }, 0);
+        assert_eq!(expectimax.choose_move(&PigPosition::new(100)), Some(PigMove::Roll));
+    }
+}
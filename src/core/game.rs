@@ -1,6 +1,8 @@
-use std::fmt::Debug;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
 
-use crate::core::r#move::Move;
+use crate::core::position_evaluator::{PositionEvaluator, SideToMovePositionEvaluator};
+use crate::core::r#move::{Move, MoveGenerator};
 
 /**
 Represents the state of a game at any point in time.
@@ -12,6 +14,102 @@ pub trait Position<PositionType, MoveType>: Copy + Debug {
      * @return The new game state resulting from applying the move to the current game state.
      */
     fn apply(&self, m: &MoveType) -> PositionType;
+
+    /// Check whether the move is legal in this position. `apply` assumes its move is legal, so moves from anywhere
+    /// but the game's own move generator should be checked first. The default accepts every move.
+    fn check_move(&self, _m: &MoveType) -> Result<(), IllegalMoveError> {
+        Ok(())
+    }
+
+    /// Apply the move if it's legal, or say why it isn't.
+    fn try_apply(&self, m: &MoveType) -> Result<PositionType, IllegalMoveError> {
+        self.check_move(m)?;
+        Ok(self.apply(m))
+    }
+}
+
+/// A position that can play a move in place and take it back again. A search over such positions can explore the
+/// whole tree with a single position, instead of copying the position at every node as `apply` does. That matters
+/// little for small boards, but a lot for positions that are expensive to copy.
+///
+/// This is optional: strategies that only need `apply` work with every position.
+pub trait UnmakePosition<MoveType>
+    where MoveType: Move
+{
+    /// Whatever `unmake_move` needs to restore the position, beyond the move itself.
+    type Undo;
+
+    /// Play the move in place. Like `apply`, this assumes the move is legal.
+    fn make_move(&mut self, m: &MoveType) -> Self::Undo;
+
+    /// Take back the move most recently played with `make_move`, restoring the position exactly as it was.
+    fn unmake_move(&mut self, m: &MoveType, undo: Self::Undo);
+}
+
+/// Why a move can't be played in a position.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum IllegalMoveError {
+    /// The game is already over, so no move can be played.
+    GameOver,
+    /// The move is for a player whose turn it isn't.
+    WrongSideToMove,
+    /// The move is for a square or column that isn't on the board.
+    OffBoard,
+    /// The square the move would play in, given by its index, already holds a piece.
+    SquareOccupied(usize),
+    /// The column the move would drop a piece into already holds as many pieces as it can.
+    ColumnFull(usize),
+    /// The card the move would play, given by its value, isn't in the player's hand.
+    CardNotInHand(usize),
+    /// A piece placed on the square with the given index wouldn't capture anything, which the rules require.
+    NothingCaptured(usize),
+    /// The move is a pass, but the player has other moves, so they can't pass.
+    PassWithMovesLeft,
+    /// The move is a chance event, like a roll of a die, but it's a player's turn to move rather than chance's.
+    NotAChanceNode,
+    /// The move is a player's, but a chance event, like a roll of a die, has to happen first.
+    AwaitingChanceEvent,
+    /// The move is a chance event that can't happen, like a roll of 7 on a six-sided die.
+    ImpossibleChanceEvent
+}
+
+impl Display for IllegalMoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IllegalMoveError::GameOver => write!(f, "the game is already over"),
+            IllegalMoveError::WrongSideToMove => write!(f, "it's the other player's turn"),
+            IllegalMoveError::OffBoard => write!(f, "the move is off the board"),
+            IllegalMoveError::SquareOccupied(square) => write!(f, "square {} is already occupied", square),
+            IllegalMoveError::ColumnFull(column) => write!(f, "column {} is already full", column),
+            IllegalMoveError::CardNotInHand(card) => write!(f, "card {} isn't in the player's hand", card),
+            IllegalMoveError::NothingCaptured(square) => write!(f, "a piece on square {} wouldn't capture anything", square),
+            IllegalMoveError::PassWithMovesLeft => write!(f, "the player can't pass while they have other moves"),
+            IllegalMoveError::NotAChanceNode => write!(f, "it's a player's turn, not a chance event's"),
+            IllegalMoveError::AwaitingChanceEvent => write!(f, "a chance event has to happen before the next move"),
+            IllegalMoveError::ImpossibleChanceEvent => write!(f, "the chance event can't happen")
+        }
+    }
+}
+
+impl Error for IllegalMoveError {}
+
+/// How a finished game turned out.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameOutcome {
+    /// The player with the given index won.
+    Win(usize),
+    Draw
+}
+
+impl GameOutcome {
+    /// The share of the win that went to the given player: 1 for a win, 0 for a loss, and one half for a draw.
+    pub fn score_for(&self, player: usize) -> f64 {
+        match *self {
+            GameOutcome::Win(winner) if winner == player => 1.0,
+            GameOutcome::Win(_) => 0.0,
+            GameOutcome::Draw => 0.5
+        }
+    }
 }
 
 /// An Oracle is a class that's not part of any Player impl that's capable of some reasoning about the state
@@ -31,5 +129,58 @@ pub trait Oracle<PositionType, MoveType>
      * @return true if the game state is a terminal (play should not continue afterward);
      *         false if the game state is an intermediate position (play should continue from this state)
      */
-    fn is_terminal(&self, game: &PositionType) -> bool;
+    fn is_terminal(&self, game: &PositionType) -> bool {
+        self.outcome(game).is_some()
+    }
+
+    /// Returns how the game ended, if the game state is terminal, or None if play should continue.
+    fn outcome(&self, game: &PositionType) -> Option<GameOutcome>;
+
+    /// Whether the player to move has to pass, because the rules leave them nothing else to do, as in Othello. The
+    /// game engine plays the move generator's pass for them without asking. The default never passes.
+    fn must_pass(&self, _game: &PositionType) -> bool {
+        false
+    }
+}
+
+/// A Game bundles everything needed to play a game: its positions and moves, and the move generator, oracle and
+/// position evaluator that reason about them. Strategies and the {@link GameEngine} can be built from a Game alone,
+/// so adding a new game means implementing this one trait.
+pub trait Game {
+    type Position: Position<Self::Position, Self::Move>;
+    type Move: Move;
+    type MoveGenerator: MoveGenerator<Self::Position, Self::Move>;
+    type Oracle: Oracle<Self::Position, Self::Move>;
+    type PositionEvaluator: PositionEvaluator<Self::Position, Self::Move>;
+
+    /// How many players take part. Players are indexed from 0, in the order the {@link Oracle} refers to them.
+    fn player_count(&self) -> usize;
+
+    /// The position every game starts from.
+    fn initial_position(&self) -> Self::Position;
+
+    fn move_generator(&self) -> Self::MoveGenerator;
+
+    fn oracle(&self) -> Self::Oracle;
+
+    /// A position evaluator that evaluates positions for the player with the given index.
+    fn position_evaluator(&self, player: usize) -> Self::PositionEvaluator;
+}
+
+/// A DeterministicGame is a {@link Game} without chance events: what happens next is always up to the player to
+/// move. Searches that assume they can play out every move themselves, like {@link MaxNMoveStrategy}, need one; games
+/// with dice and the like are searched with {@link ExpectimaxMoveStrategy} or {@link MctsMoveStrategy} instead.
+pub trait DeterministicGame: Game {}
+
+/// An AlternatingGame is a {@link DeterministicGame} for two players who take turns, where whatever is good for one
+/// player is equally bad for the other. Minimax and the searches built on it, like {@link AlphaBetaMoveStrategy} and
+/// {@link NegamaxMoveStrategy}, need one.
+///
+/// Only in such a game does evaluating a position for whoever is to move make sense, so the side-to-move evaluator
+/// that negamax searches use belongs here rather than to every {@link Game}.
+pub trait AlternatingGame: DeterministicGame {
+    type SideToMovePositionEvaluator: SideToMovePositionEvaluator<Self::Position, Self::Move>;
+
+    /// A position evaluator that evaluates positions for whichever player is about to move in them.
+    fn side_to_move_position_evaluator(&self) -> Self::SideToMovePositionEvaluator;
 }
@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+use std::sync::Arc;
+use crate::core::engine::{GameEngine, GameEngineError};
+use crate::core::game::{Game, GameOutcome};
+use crate::core::player::Player;
+
+/// Makes a new player for a game of a match, given the game and the index of the player it plays as. Factories are
+/// shared, so that the same factories can make the players of games on different threads, like the games of a
+/// {@link Tournament}.
+pub type PlayerFactory<GameType> = Arc<dyn Fn(&GameType, usize) -> Box<dyn Player<<GameType as Game>::Position, <GameType as Game>::Move>> + Send + Sync>;
+
+/// The number of standard deviations either side of the mean that a 95% confidence interval spans.
+const CONFIDENCE_95: f64 = 1.96;
+
+/// A Match plays a number of games between two players, to compare their strategies.
+///
+/// Every game gets new players from the players' factories, so that no game's player remembers another game. The
+/// players take turns being player 0, so that neither gets the advantage of moving first more often. A match can
+/// start its games from a set of opening positions instead of the game's initial position, which keeps deterministic
+/// players from playing the same game over and over: each opening is played twice in a row, once with each player
+/// moving first, and the openings are used in turn.
+pub struct Match<GameType>
+    where GameType: Game
+{
+    game: GameType,
+    players: [PlayerFactory<GameType>; 2],
+    games: usize,
+    openings: Vec<GameType::Position>,
+    seed: u64
+}
+
+impl<GameType> Match<GameType>
+    where GameType: Game,
+          GameType::Position: 'static,
+          GameType::Move: 'static
+{
+    /// Set up a two-game match between the players the factories make. Results are reported from the first player's
+    /// point of view.
+    pub fn new<PlayerFactoryType, OpponentFactoryType>(game: GameType, player: PlayerFactoryType, opponent: OpponentFactoryType) -> Match<GameType>
+        where PlayerFactoryType: Fn(&GameType, usize) -> Box<dyn Player<GameType::Position, GameType::Move>> + Send + Sync + 'static,
+              OpponentFactoryType: Fn(&GameType, usize) -> Box<dyn Player<GameType::Position, GameType::Move>> + Send + Sync + 'static {
+        Match::between(game, Arc::new(player), Arc::new(opponent))
+    }
+
+    /// Set up a two-game match between the players made by factories that are already shared, like those of the
+    /// {@link Entrant}s of a tournament.
+    pub fn between(game: GameType, player: PlayerFactory<GameType>, opponent: PlayerFactory<GameType>) -> Match<GameType> {
+        Match {
+            game,
+            players: [player, opponent],
+            games: 2,
+            openings: vec![],
+            seed: 0
+        }
+    }
+
+    /// Play the given number of games. An even number gives each player the first move equally often.
+    pub fn with_games(mut self, games: usize) -> Match<GameType> {
+        self.games = games;
+        self
+    }
+
+    /// Start the games from the given positions instead of the game's initial position.
+    pub fn with_openings(mut self, openings: Vec<GameType::Position>) -> Match<GameType> {
+        self.openings = openings;
+        self
+    }
+
+    /// Seed the chance events of the games. Each game gets its own seed, counting up from this one.
+    pub fn with_seed(mut self, seed: u64) -> Match<GameType> {
+        self.seed = seed;
+        self
+    }
+
+    /// Play every game of the match, and return the results, or the error that stopped a game. A game the engine
+    /// can't find an outcome for counts as a draw.
+    pub fn play(&self) -> Result<MatchResult, GameEngineError> {
+        let mut result = MatchResult::default();
+        for game_index in 0..self.games {
+            // the first player is player 0 in even games, and player 1 in odd ones
+            let player_index = game_index % 2;
+            let (player_0, player_1) = match player_index {
+                0 => (&self.players[0], &self.players[1]),
+                _ => (&self.players[1], &self.players[0])
+            };
+            let players = vec![player_0(&self.game, 0), player_1(&self.game, 1)];
+            let mut engine = GameEngine::new(&self.game, players).with_seed(self.seed.wrapping_add(game_index as u64));
+            if !self.openings.is_empty() {
+                engine = engine.with_initial_position(self.openings[(game_index / 2) % self.openings.len()]);
+            }
+            result.add_game(engine.run()?.outcome, player_index);
+        }
+        Ok(result)
+    }
+}
+
+/// How a match went, from the first player's point of view.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MatchResult {
+    pub wins: usize,
+    pub losses: usize,
+    pub draws: usize
+}
+
+impl MatchResult {
+    pub fn games(&self) -> usize {
+        self.wins + self.losses + self.draws
+    }
+
+    /// Add a game in which the first player was the player with the given index. A game without an outcome counts
+    /// as a draw.
+    pub fn add_game(&mut self, outcome: Option<GameOutcome>, player_index: usize) {
+        let score = outcome.map_or(0.5, |outcome| outcome.score_for(player_index));
+        if score == 1.0 {
+            self.wins += 1;
+        } else if score == 0.0 {
+            self.losses += 1;
+        } else {
+            self.draws += 1;
+        }
+    }
+
+    /// The share of the points the first player won, counting a draw as half a win, between 0 and 1.
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.5,
+            games => (self.wins as f64 + self.draws as f64 / 2.0) / games as f64
+        }
+    }
+
+    /// The 95% confidence interval of the first player's expected score, as its lowest and highest values. If the
+    /// interval lies above one half, the first player is very likely the stronger one.
+    ///
+    /// This is the Wilson score interval, which stays wide for short matches, even ones where one player won every
+    /// game. It treats the score as a share of wins, so draws make it a little wider than it needs to be.
+    pub fn confidence_interval(&self) -> (f64, f64) {
+        let games = self.games();
+        if games == 0 {
+            return (0.0, 1.0);
+        }
+        let games = games as f64;
+        let score = self.score();
+        let z_squared = CONFIDENCE_95 * CONFIDENCE_95;
+        let center = (score + z_squared / (2.0 * games)) / (1.0 + z_squared / games);
+        let margin = CONFIDENCE_95 / (1.0 + z_squared / games)
+            * (score * (1.0 - score) / games + z_squared / (4.0 * games * games)).sqrt();
+        ((center - margin).max(0.0), (center + margin).min(1.0))
+    }
+}
+
+impl Display for MatchResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (low, high) = self.confidence_interval();
+        write!(f, "+{} -{} ={}, score {:.3} (95% confidence interval {:.3} to {:.3})", self.wins, self.losses, self.draws, self.score(), low, high)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::Position;
+    use crate::core::player::Player;
+    use crate::core::r#match::{Match, MatchResult};
+    use crate::core::test_support::negamax_player;
+    use crate::tictactoe::{Piece, TicTacToe, TicTacToeMove, TicTacToePosition};
+
+    /// Plays the first empty square.
+    struct FirstSquarePlayer {}
+
+    impl Player<TicTacToePosition, TicTacToeMove> for FirstSquarePlayer {
+        fn pick_move(&self, game: &TicTacToePosition) -> Option<TicTacToeMove> {
+            let piece = match game.board.iter().filter(|square| square.is_some()).count() % 2 {
+                0 => Piece::X,
+                _ => Piece::O
+            };
+            (0..9).find(|&position| game.board[position].is_none()).map(|position| TicTacToeMove { position, piece })
+        }
+    }
+
+    #[test]
+    pub fn test_match_alternates_players() {
+        let first_square = |_: &TicTacToe, _| Box::new(FirstSquarePlayer {}) as Box<dyn Player<_, _>>;
+        let result = Match::new(TicTacToe {}, negamax_player, first_square)
+            .with_games(4)
+            .play()
+            .unwrap();
+        assert_eq!(result, MatchResult { wins: 4, losses: 0, draws: 0 });
+        assert_eq!(result.score(), 1.0);
+    }
+
+    #[test]
+    pub fn test_match_plays_openings() {
+        // X in the corner and O on the edge beside it, which loses for O
+        let opening = TicTacToePosition::new()
+            .apply(&TicTacToeMove { position: 0, piece: Piece::X })
+            .apply(&TicTacToeMove { position: 1, piece: Piece::O });
+        let result = Match::new(TicTacToe {}, negamax_player, negamax_player)
+            .with_games(2)
+            .with_openings(vec![opening])
+            .play()
+            .unwrap();
+        // each player wins the game where they're X
+        assert_eq!(result, MatchResult { wins: 1, losses: 1, draws: 0 });
+    }
+
+    #[test]
+    pub fn test_confidence_interval() {
+        let result = MatchResult { wins: 60, losses: 30, draws: 10 };
+        assert_eq!(result.score(), 0.65);
+        let (low, high) = result.confidence_interval();
+        assert!(low > 0.5 && low < 0.65);
+        assert!(high > 0.65 && high < 0.8);
+
+        // all draws say the players are even, but not exactly how even
+        let even = MatchResult { wins: 0, losses: 0, draws: 10 };
+        let (low, high) = even.confidence_interval();
+        assert!(low < 0.5 && high > 0.5);
+        assert!((0.5 - low - (high - 0.5)).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn test_confidence_interval_of_a_sweep() {
+        // four games are too few to be sure of anything, even if one player won them all
+        let sweep = MatchResult { wins: 4, losses: 0, draws: 0 };
+        let (low, high) = sweep.confidence_interval();
+        assert!((high - 1.0).abs() < 1e-9);
+        assert!(low > 0.5 && low < 0.6);
+
+        let (low, high) = MatchResult { wins: 0, losses: 4, draws: 0 }.confidence_interval();
+        assert!(low.abs() < 1e-9);
+        assert!(high > 0.4 && high < 0.5);
+    }
+}
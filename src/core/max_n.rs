@@ -0,0 +1,143 @@
+use crate::core::game::{DeterministicGame, Position, Oracle};
+use crate::core::move_strategy::MoveStrategy;
+use crate::core::position_evaluator::{EachPlayerPositionEvaluator, MultiPlayerPositionEvaluator, PositionEvaluation};
+use crate::core::r#move::MoveGenerator;
+
+/// A MaxNMoveStrategy picks moves with the max^n algorithm, which generalizes minimax to any number of players.
+///
+/// Every position is scored for each player at once. At each node of the search, the player to move picks the move
+/// whose position scores best for them, and that position's scores, for every player, become the node's scores.
+/// With two players whose interests are exactly opposed, this picks the same moves as minimax.
+///
+/// Players' interests aren't opposed in general, so one player's loss doesn't bound another's gain, and the search
+/// can't prune the way alpha-beta does. When a player has several equally good moves, it takes the first one in the
+/// move generator's order.
+pub struct MaxNMoveStrategy<GameType>
+    where GameType: DeterministicGame
+{
+    position_evaluator: EachPlayerPositionEvaluator<GameType>,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize
+}
+
+impl<GameType> MaxNMoveStrategy<GameType>
+    where GameType: DeterministicGame
+{
+    pub fn new(position_evaluator: EachPlayerPositionEvaluator<GameType>, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> MaxNMoveStrategy<GameType> {
+        MaxNMoveStrategy {
+            position_evaluator,
+            move_generator,
+            oracle,
+            max_depth: usize::MAX
+        }
+    }
+
+    /// Create a strategy for the given game. Positions are scored for every player, so the strategy can play for any
+    /// of them.
+    pub fn for_game(game: &GameType) -> MaxNMoveStrategy<GameType> {
+        MaxNMoveStrategy::new(EachPlayerPositionEvaluator::for_game(game), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies. Positions at that depth are scored with the position evaluator's
+    /// estimates instead of being searched further. The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> MaxNMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The evaluations of the position after the move, for each player.
+    fn evaluate_move(&self, game: &GameType::Position, r#move: &GameType::Move, depth: usize) -> Vec<PositionEvaluation> {
+        let new_position = &game.apply(r#move);
+        let position_evaluations = match self.choose_move_recursive(new_position, depth - 1) {
+            None => self.position_evaluator.evaluate_for_each_player(new_position),
+            Some((_, recursive_evaluations)) => recursive_evaluations
+        };
+        position_evaluations.iter().map(PositionEvaluation::add_ply).collect()
+    }
+
+    /// Returns None if the position is a leaf of the search, in which case the caller should evaluate the position
+    /// directly. That includes positions where the oracle can't say whose turn it is, and where the player to move
+    /// has no moves.
+    fn choose_move_recursive(&self, game: &GameType::Position, depth: usize) -> Option<(GameType::Move, Vec<PositionEvaluation>)> {
+        if depth == 0 || self.oracle.is_terminal(game) {
+            return None;
+        }
+        let player = self.oracle.next_player(game)?;
+
+        let mut best: Option<(GameType::Move, Vec<PositionEvaluation>)> = None;
+        for r#move in self.move_generator.get_moves_or_pass(game) {
+            let new_position_evaluations = self.evaluate_move(game, &r#move, depth);
+            let is_better = match &best {
+                None => true,
+                Some((_, best_position_evaluations)) => new_position_evaluations[player].improves_on(&best_position_evaluations[player])
+            };
+            if is_better {
+                best = Some((r#move, new_position_evaluations));
+            }
+        }
+        best
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for MaxNMoveStrategy<GameType>
+    where GameType: DeterministicGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        let player_to_move = self.oracle.next_player(game)?;
+        self.choose_move_recursive(game, self.max_depth.max(1))
+            .map(|(best_move, evaluations)| (best_move, evaluations.get(player_to_move).copied()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{Game, Oracle, Position};
+    use crate::core::max_n::MaxNMoveStrategy;
+    use crate::core::move_strategy::{MoveStrategy, NegamaxMoveStrategy};
+    use crate::core::r#move::MoveGenerator;
+    use crate::core::test_support::{Countdown, CountdownPosition};
+    use crate::tictactoe::{TicTacToe, TicTacToeMoveGenerator, TicTacToeOracle, TicTacToePosition};
+
+    #[test]
+    pub fn test_max_n_plays_for_the_player_to_move() {
+        let max_n = MaxNMoveStrategy::for_game(&Countdown::default());
+        for next_player in 0..3 {
+            assert_eq!(max_n.choose_move(&CountdownPosition { counters: 2, next_player }).unwrap().take, 2);
+        }
+    }
+
+    #[test]
+    pub fn test_max_n_counts_on_losing_players_to_lose_slowly() {
+        // Taking two leaves three, and whatever player 1 does, player 2 wins. Taking one leaves four, and player 1
+        // can't win either way, but staves off the loss longest by taking one, so that player 0 wins.
+        let game = Countdown { counters: 5 };
+        let max_n = MaxNMoveStrategy::for_game(&game);
+        let game = game.initial_position();
+        assert_eq!(max_n.choose_move(&game).unwrap().take, 1);
+    }
+
+    #[test]
+    pub fn test_max_n_matches_negamax_for_two_players() {
+        let negamax = NegamaxMoveStrategy::for_game(&TicTacToe {});
+        let max_n = MaxNMoveStrategy::for_game(&TicTacToe {});
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(negamax.choose_move(&game).unwrap().position, max_n.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+}
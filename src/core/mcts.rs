@@ -0,0 +1,361 @@
+use std::cell::RefCell;
+use std::time::{Duration, Instant};
+use crate::core::game::{Game, Position, Oracle};
+use crate::core::move_strategy::{MoveStrategy, ObservationMoveStrategy};
+use crate::core::observation::{ImperfectInformationGame, ObservationFor, ObservationModel};
+use crate::core::r#move::{Move, MoveGenerator};
+use crate::core::random::Random;
+
+/// How much searching an {@link MctsMoveStrategy} does before it picks a move.
+#[derive(Copy, Clone, Debug)]
+pub enum SearchBudget {
+    /// Run a fixed number of iterations. Useful when results need to be reproducible.
+    Iterations(usize),
+    /// Run as many iterations as fit in the given time.
+    Time(Duration)
+}
+
+/// A node in the search tree. Rewards are always from the point of view of the player choosing the move at the root.
+struct Node<PositionType, MoveType> {
+    position: PositionType,
+    r#move: Option<MoveType>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried_moves: Vec<MoveType>,
+    root_player_to_move: bool,
+    visits: usize,
+    total_reward: f64
+}
+
+/// An MctsMoveStrategy picks moves by Monte Carlo Tree Search, using the UCT (Upper Confidence bounds applied to
+/// Trees) rule to decide which part of the tree to explore next.
+///
+/// Each iteration walks down the tree to a position that still has untried moves, adds one of them to the tree, plays
+/// random moves from there until the game ends, and credits the result to every position along the way. Unlike the
+/// minimax family, it never needs an estimate of an unfinished position: all it needs is the oracle's verdict on how
+/// each playout ended. In games with chance events, the tree stops at chance nodes, and what happens after them is left
+/// to the playouts.
+///
+/// The random playouts are driven by a seeded generator, so a strategy with a given seed and an iteration budget
+/// always makes the same choices.
+pub struct MctsMoveStrategy<GameType>
+    where GameType: Game
+{
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    budget: SearchBudget,
+    exploration_constant: f64,
+    random: RefCell<Random>
+}
+
+impl<GameType> MctsMoveStrategy<GameType>
+    where GameType: Game
+{
+    pub fn new(move_generator: GameType::MoveGenerator, oracle: GameType::Oracle, budget: SearchBudget) -> MctsMoveStrategy<GameType> {
+        MctsMoveStrategy {
+            move_generator,
+            oracle,
+            budget,
+            exploration_constant: std::f64::consts::SQRT_2,
+            random: RefCell::new(Random::new(0))
+        }
+    }
+
+    pub fn for_game(game: &GameType, budget: SearchBudget) -> MctsMoveStrategy<GameType> {
+        MctsMoveStrategy::new(game.move_generator(), game.oracle(), budget)
+    }
+
+    /// Set how strongly the search favors rarely-visited moves over moves that have done well so far. Defaults to
+    /// the square root of 2, the theoretical value for rewards between 0 and 1.
+    pub fn with_exploration_constant(mut self, exploration_constant: f64) -> MctsMoveStrategy<GameType> {
+        self.exploration_constant = exploration_constant;
+        self
+    }
+
+    /// Seed the generator that drives expansion and playouts. Defaults to 0.
+    pub fn with_seed(self, seed: u64) -> MctsMoveStrategy<GameType> {
+        self.random.replace(Random::new(seed));
+        self
+    }
+
+    fn new_node(&self, position: GameType::Position, r#move: Option<GameType::Move>, parent: Option<usize>, root_player: Option<usize>) -> Node<GameType::Position, GameType::Move> {
+        let untried_moves = match self.oracle.is_terminal(&position) {
+            true => vec![],
+            false => self.move_generator.get_moves_or_pass(&position)
+        };
+        Node {
+            position,
+            r#move,
+            parent,
+            children: vec![],
+            untried_moves,
+            root_player_to_move: self.oracle.next_player(&position) == root_player,
+            visits: 0,
+            total_reward: 0.0
+        }
+    }
+
+    /// Pick the child of the node with the highest upper confidence bound, from the point of view of the player
+    /// choosing the move at the node.
+    fn select_child(&self, tree: &[Node<GameType::Position, GameType::Move>], node: usize) -> usize {
+        let parent = &tree[node];
+        let log_visits = (parent.visits as f64).ln();
+        let upper_confidence_bound = |child: &Node<GameType::Position, GameType::Move>| {
+            let mean_reward = child.total_reward / child.visits as f64;
+            let exploitation = if parent.root_player_to_move { mean_reward } else { 1.0 - mean_reward };
+            exploitation + self.exploration_constant * (log_visits / child.visits as f64).sqrt()
+        };
+
+        let mut best_child = parent.children[0];
+        let mut best_bound = upper_confidence_bound(&tree[best_child]);
+        for &child in &parent.children[1..] {
+            let bound = upper_confidence_bound(&tree[child]);
+            if bound > best_bound {
+                best_bound = bound;
+                best_child = child;
+            }
+        }
+        best_child
+    }
+
+    /// Play random moves until the game ends, and return the reward for the player choosing the move at the root.
+    /// Chance events happen with their own probabilities.
+    fn playout(&self, position: &GameType::Position, root_player: Option<usize>, random: &mut Random) -> f64 {
+        let mut position = *position;
+        while !self.oracle.is_terminal(&position) {
+            let chance_events = self.move_generator.get_chance_events(&position);
+            if !chance_events.is_empty() {
+                position = position.apply(&random.pick_weighted(&chance_events));
+                continue;
+            }
+            let moves = self.move_generator.get_moves_or_pass(&position);
+            if moves.is_empty() {
+                break;
+            }
+            position = position.apply(&moves[random.below(moves.len())]);
+        }
+        match (self.oracle.outcome(&position), root_player) {
+            (Some(outcome), Some(root_player)) => outcome.score_for(root_player),
+            _ => 0.5
+        }
+    }
+
+    /// Search the tree below the position, and return each move from it with the number of times the search visited
+    /// it, in the order the moves were first tried. Returns no moves if the position has none.
+    fn search(&self, game: &GameType::Position) -> Vec<(GameType::Move, usize)> {
+        let root_player = self.oracle.next_player(game);
+        let mut tree = vec![self.new_node(*game, None, None, root_player)];
+        if tree[0].untried_moves.is_empty() {
+            return vec![];
+        }
+
+        let mut random = self.random.borrow_mut();
+        match self.budget {
+            SearchBudget::Iterations(iterations) => {
+                for _ in 0..iterations.max(1) {
+                    self.iterate(&mut tree, root_player, &mut random);
+                }
+            },
+            SearchBudget::Time(time_budget) => {
+                let deadline = Instant::now() + time_budget;
+                // always run at least one iteration, so there's a move to play
+                self.iterate(&mut tree, root_player, &mut random);
+                while Instant::now() < deadline {
+                    self.iterate(&mut tree, root_player, &mut random);
+                }
+            }
+        }
+
+        tree[0].children.iter()
+            .map(|&child| match tree[child].r#move {
+                Some(r#move) => (r#move, tree[child].visits),
+                None => unreachable!("Every node below the root is reached by a move")
+            })
+            .collect()
+    }
+
+    fn iterate(&self, tree: &mut Vec<Node<GameType::Position, GameType::Move>>, root_player: Option<usize>, random: &mut Random) {
+        // selection
+        let mut node = 0;
+        while tree[node].untried_moves.is_empty() && !tree[node].children.is_empty() {
+            node = self.select_child(tree, node);
+        }
+
+        // expansion
+        if !tree[node].untried_moves.is_empty() {
+            let index = random.below(tree[node].untried_moves.len());
+            let r#move = tree[node].untried_moves.swap_remove(index);
+            let position = tree[node].position.apply(&r#move);
+            let child = tree.len();
+            tree.push(self.new_node(position, Some(r#move), Some(node), root_player));
+            tree[node].children.push(child);
+            node = child;
+        }
+
+        // simulation
+        let reward = self.playout(&tree[node].position, root_player, random);
+
+        // backpropagation
+        let mut current = Some(node);
+        while let Some(index) = current {
+            tree[index].visits += 1;
+            tree[index].total_reward += reward;
+            current = tree[index].parent;
+        }
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for MctsMoveStrategy<GameType>
+    where GameType: Game {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        most_visited(&self.search(game))
+    }
+}
+
+/// The most-visited of the moves, which is the one the search had the most confidence in. Ties go to the first.
+fn most_visited<MoveType>(move_visits: &[(MoveType, usize)]) -> Option<MoveType>
+    where MoveType: Move {
+    let mut best = *move_visits.first()?;
+    for &(r#move, visits) in &move_visits[1..] {
+        if visits > best.1 {
+            best = (r#move, visits);
+        }
+    }
+    Some(best.0)
+}
+
+/// A DeterminizedMctsMoveStrategy plays games with hidden information by guessing at what it can't see.
+///
+/// It determinizes the player's observation several times, searches each of the resulting positions with an
+/// {@link MctsMoveStrategy} as if nothing were hidden, and plays the move the searches visited most in total. The
+/// guesses are often wrong, but the move that does best across many of them tends to do well in the real position.
+///
+/// Searching each guess as if it were the truth lets the searches assume knowledge the player doesn't have, so the
+/// strategy can't bluff, and can't see the point of keeping its own secrets.
+pub struct DeterminizedMctsMoveStrategy<GameType>
+    where GameType: ImperfectInformationGame,
+          GameType::Move: PartialEq
+{
+    mcts: MctsMoveStrategy<GameType>,
+    observation_model: GameType::ObservationModel,
+    determinizations: usize,
+    random: RefCell<Random>
+}
+
+impl<GameType> DeterminizedMctsMoveStrategy<GameType>
+    where GameType: ImperfectInformationGame,
+          GameType::Move: PartialEq
+{
+    /// Create a strategy that searches each determinization within the given budget.
+    pub fn new(move_generator: GameType::MoveGenerator, oracle: GameType::Oracle, observation_model: GameType::ObservationModel, budget: SearchBudget) -> DeterminizedMctsMoveStrategy<GameType> {
+        DeterminizedMctsMoveStrategy {
+            mcts: MctsMoveStrategy::new(move_generator, oracle, budget),
+            observation_model,
+            determinizations: 10,
+            random: RefCell::new(Random::new(0))
+        }
+    }
+
+    pub fn for_game(game: &GameType, budget: SearchBudget) -> DeterminizedMctsMoveStrategy<GameType> {
+        DeterminizedMctsMoveStrategy::new(game.move_generator(), game.oracle(), game.observation_model(), budget)
+    }
+
+    /// Set how many determinizations to search for each move (at least one). Defaults to 10.
+    pub fn with_determinizations(mut self, determinizations: usize) -> DeterminizedMctsMoveStrategy<GameType> {
+        self.determinizations = determinizations.max(1);
+        self
+    }
+
+    /// Seed the generators that drive determinization and the searches. Defaults to 0.
+    pub fn with_seed(mut self, seed: u64) -> DeterminizedMctsMoveStrategy<GameType> {
+        self.random.replace(Random::new(seed));
+        self.mcts = self.mcts.with_seed(seed);
+        self
+    }
+}
+
+impl<GameType> ObservationMoveStrategy<ObservationFor<GameType>, GameType::Move>
+for DeterminizedMctsMoveStrategy<GameType>
+    where GameType: ImperfectInformationGame,
+          GameType::Move: PartialEq {
+    fn choose_move(&self, observation: &ObservationFor<GameType>) -> Option<GameType::Move> {
+        let mut move_visits: Vec<(GameType::Move, usize)> = vec![];
+        for _ in 0..self.determinizations {
+            let position = self.observation_model.determinize(observation, &mut self.random.borrow_mut());
+            for (r#move, visits) in self.mcts.search(&position) {
+                match move_visits.iter_mut().find(|(seen_move, _)| *seen_move == r#move) {
+                    Some((_, total_visits)) => *total_visits += visits,
+                    None => move_visits.push((r#move, visits))
+                }
+            }
+        }
+        most_visited(&move_visits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::mcts::{DeterminizedMctsMoveStrategy, MctsMoveStrategy, SearchBudget};
+    use crate::core::move_strategy::{MoveStrategy, ObservationMoveStrategy};
+    use crate::core::observation::ObservationModel;
+    use crate::goofspiel::{Goofspiel, GoofspielObservationModel, GoofspielPosition};
+    use crate::tictactoe::{Piece, TicTacToe, TicTacToePosition};
+
+    fn mcts(seed: u64) -> MctsMoveStrategy<TicTacToe> {
+        MctsMoveStrategy::for_game(&TicTacToe {}, SearchBudget::Iterations(2000))
+            .with_seed(seed)
+    }
+
+    #[test]
+    pub fn test_mcts_takes_the_win() {
+        //  X | X |
+        // -----------
+        //  O | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), None, Some(Piece::O), Some(Piece::O), None, None, None, None], 1);
+        assert_eq!(mcts(7).choose_move(&game).unwrap().position, 2);
+    }
+
+    #[test]
+    pub fn test_mcts_blocks_the_loss() {
+        //  X |   |
+        // -----------
+        //  O | O |
+        // -----------
+        //  X |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), None, None, Some(Piece::O), Some(Piece::O), None, Some(Piece::X), None, None], 1);
+        assert_eq!(mcts(7).choose_move(&game).unwrap().position, 5);
+    }
+
+    #[test]
+    pub fn test_mcts_plays_for_the_side_to_move() {
+        //  X | X |
+        // -----------
+        //  O | O |
+        // -----------
+        //  X |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), None, Some(Piece::O), Some(Piece::O), None, Some(Piece::X), None, None], 0);
+        assert_eq!(mcts(7).choose_move(&game).unwrap().position, 5);
+    }
+
+    #[test]
+    pub fn test_mcts_is_reproducible() {
+        let game = TicTacToePosition::new();
+        for seed in 0..5 {
+            assert_eq!(mcts(seed).choose_move(&game).unwrap().position, mcts(seed).choose_move(&game).unwrap().position);
+        }
+    }
+
+    #[test]
+    pub fn test_determinized_mcts_plays_well_whatever_the_hidden_bid() {
+        // Player 0 has bid 1 or 2 for the prize of 2, and the last prize is 3. Bidding 3 only wins if player 0 bid 2,
+        // but bidding 1 saves the 3 for the last prize, and wins either way.
+        let position = GoofspielPosition { hands: [0b011, 0b101], round: 1, bid: Some(2), ..GoofspielPosition::new(3).unwrap() };
+        let observation = GoofspielObservationModel {}.observe(&position, 1);
+        let determinized_mcts = DeterminizedMctsMoveStrategy::for_game(&Goofspiel::new(3).unwrap(), SearchBudget::Iterations(200))
+            .with_seed(7);
+        assert_eq!(determinized_mcts.choose_move(&observation).unwrap().card, 1);
+    }
+}
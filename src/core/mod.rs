@@ -3,4 +3,19 @@ pub mod r#move;
 pub mod engine;
 pub mod game;
 pub mod move_strategy;
+pub mod move_ordering;
 pub mod position_evaluator;
+pub mod random;
+pub mod mcts;
+pub mod max_n;
+pub mod expectimax;
+pub mod observation;
+pub mod observer;
+pub mod record;
+pub mod r#match;
+pub mod tournament;
+pub mod parallel;
+pub mod transposition_table;
+pub mod zobrist;
+#[cfg(test)]
+pub(crate) mod test_support;
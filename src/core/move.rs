@@ -8,4 +8,30 @@ pub trait Move: Copy + Debug {
 pub trait MoveGenerator<PositionType: Position<PositionType, MoveType>, MoveType: Move> {
     /// Produce a list of all possible proximal moves for a given game position.
     fn get_moves(&self, game: &PositionType) -> Vec<MoveType>;
+
+    /// Produce the chance events that can happen in a game position, like the faces of a die being rolled, each with
+    /// the probability that it happens. The probabilities should add up to 1.
+    ///
+    /// A position with chance events is a chance node: no player moves in it, and one of the events is played
+    /// instead, picked at random. The default has no chance events, which suits games without any chance.
+    fn get_chance_events(&self, _game: &PositionType) -> Vec<(MoveType, f64)> {
+        vec![]
+    }
+
+    /// The move that passes the turn, if the player to move in the game position has no other moves and the rules
+    /// let them pass, as in Othello. Passes shouldn't be included in `get_moves`. The default never passes.
+    fn get_pass(&self, _game: &PositionType) -> Option<MoveType> {
+        None
+    }
+
+    /// The moves for the player to move in a game position, or the pass if there are no others. Strategies should
+    /// search these rather than `get_moves`, so that they play through passes. The list is empty if the player has
+    /// no moves and can't pass.
+    fn get_moves_or_pass(&self, game: &PositionType) -> Vec<MoveType> {
+        let moves = self.get_moves(game);
+        match (moves.is_empty(), self.get_pass(game)) {
+            (true, Some(pass)) => vec![pass],
+            _ => moves
+        }
+    }
 }
@@ -0,0 +1,259 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use crate::core::game::Position;
+use crate::core::r#move::Move;
+
+/// What a search knows about a position when it asks for the position's moves to be ordered.
+pub struct OrderingContext<MoveType: Move> {
+    /// How many plies below the root of the search the position is.
+    pub ply: usize,
+    /// The best move found the last time the position was searched, if the search remembers it (e.g., in a
+    /// transposition table).
+    pub hash_move: Option<MoveType>
+}
+
+/// A MoveOrderer reorders the moves of a position so that the ones most likely to be best are searched first.
+///
+/// The order of moves doesn't change what a minimax search finds, but alpha-beta pruning and its descendants prune
+/// far more when the best move comes first. Orderers should sort stably, so that they can be chained with
+/// {@link ChainedMoveOrderer} without undoing each other's work.
+pub trait MoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+    fn order_moves(&self, game: &PositionType, moves: &mut [MoveType], context: &OrderingContext<MoveType>);
+
+    /// Called by the search whenever a move turns out to be so good that the rest of the position's moves are pruned,
+    /// for orderers that learn from the search as it goes. `depth` is how many plies deep the position was searched.
+    fn record_cutoff(&self, _game: &PositionType, _move: &MoveType, _depth: usize, _ply: usize) {}
+}
+
+/// Orders moves by several orderers at once. Moves are ordered primarily by the first orderer, with ties broken by
+/// the second, and so on.
+pub struct ChainedMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+    move_orderers: Vec<Box<dyn MoveOrderer<PositionType, MoveType>>>
+}
+
+impl<PositionType, MoveType> ChainedMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+    pub fn new(move_orderers: Vec<Box<dyn MoveOrderer<PositionType, MoveType>>>) -> ChainedMoveOrderer<PositionType, MoveType> {
+        ChainedMoveOrderer { move_orderers }
+    }
+}
+
+impl<PositionType, MoveType> MoveOrderer<PositionType, MoveType> for ChainedMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+    fn order_moves(&self, game: &PositionType, moves: &mut [MoveType], context: &OrderingContext<MoveType>) {
+        // since every orderer sorts stably, the last one applied has the final say
+        for move_orderer in self.move_orderers.iter().rev() {
+            move_orderer.order_moves(game, moves, context);
+        }
+    }
+
+    fn record_cutoff(&self, game: &PositionType, r#move: &MoveType, depth: usize, ply: usize) {
+        for move_orderer in &self.move_orderers {
+            move_orderer.record_cutoff(game, r#move, depth, ply);
+        }
+    }
+}
+
+/// Searches the best move from the previous search of the position first. When a search is repeated at increasing
+/// depths, this is usually the best move of the new search too.
+pub struct HashMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    phantom_game: PhantomData<PositionType>,
+    phantom_move: PhantomData<MoveType>
+}
+
+impl<PositionType, MoveType> HashMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    pub fn new() -> HashMoveOrderer<PositionType, MoveType> {
+        HashMoveOrderer {
+            phantom_game: PhantomData,
+            phantom_move: PhantomData
+        }
+    }
+}
+
+impl<PositionType, MoveType> Default for HashMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    fn default() -> Self {
+        HashMoveOrderer::new()
+    }
+}
+
+impl<PositionType, MoveType> MoveOrderer<PositionType, MoveType> for HashMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    fn order_moves(&self, _game: &PositionType, moves: &mut [MoveType], context: &OrderingContext<MoveType>) {
+        if let Some(hash_move) = context.hash_move {
+            moves.sort_by_key(|r#move| *r#move != hash_move);
+        }
+    }
+}
+
+/// Searches killer moves first: the most recent moves that caused a cutoff at the same ply elsewhere in the tree.
+/// A move that refutes one of the opponent's moves often refutes its siblings too.
+pub struct KillerMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    phantom_game: PhantomData<PositionType>,
+    killers_per_ply: usize,
+    killers: RefCell<Vec<Vec<MoveType>>>
+}
+
+impl<PositionType, MoveType> KillerMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    /// Remember up to `killers_per_ply` killer moves at each ply. Two is the usual choice.
+    pub fn new(killers_per_ply: usize) -> KillerMoveOrderer<PositionType, MoveType> {
+        KillerMoveOrderer {
+            phantom_game: PhantomData,
+            killers_per_ply,
+            killers: RefCell::new(vec![])
+        }
+    }
+}
+
+impl<PositionType, MoveType> MoveOrderer<PositionType, MoveType> for KillerMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + PartialEq {
+    fn order_moves(&self, _game: &PositionType, moves: &mut [MoveType], context: &OrderingContext<MoveType>) {
+        if let Some(killers) = self.killers.borrow().get(context.ply) {
+            moves.sort_by_key(|r#move| killers.iter().position(|killer| killer == r#move).unwrap_or(killers.len()));
+        }
+    }
+
+    fn record_cutoff(&self, _game: &PositionType, r#move: &MoveType, _depth: usize, ply: usize) {
+        let mut killers = self.killers.borrow_mut();
+        if killers.len() <= ply {
+            killers.resize_with(ply + 1, Vec::new);
+        }
+        let ply_killers = &mut killers[ply];
+        if let Some(index) = ply_killers.iter().position(|killer| killer == r#move) {
+            ply_killers.remove(index);
+        }
+        ply_killers.insert(0, *r#move);
+        ply_killers.truncate(self.killers_per_ply);
+    }
+}
+
+/// Searches moves that have caused the most cutoffs anywhere in the tree first. Cutoffs near the root count for more,
+/// since they prune bigger subtrees.
+pub struct HistoryMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + Eq + Hash {
+    phantom_game: PhantomData<PositionType>,
+    history: RefCell<HashMap<MoveType, usize>>
+}
+
+impl<PositionType, MoveType> HistoryMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + Eq + Hash {
+    pub fn new() -> HistoryMoveOrderer<PositionType, MoveType> {
+        HistoryMoveOrderer {
+            phantom_game: PhantomData,
+            history: RefCell::new(HashMap::new())
+        }
+    }
+}
+
+impl<PositionType, MoveType> Default for HistoryMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + Eq + Hash {
+    fn default() -> Self {
+        HistoryMoveOrderer::new()
+    }
+}
+
+impl<PositionType, MoveType> MoveOrderer<PositionType, MoveType> for HistoryMoveOrderer<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move + Eq + Hash {
+    fn order_moves(&self, _game: &PositionType, moves: &mut [MoveType], _context: &OrderingContext<MoveType>) {
+        let history = self.history.borrow();
+        moves.sort_by_key(|r#move| std::cmp::Reverse(history.get(r#move).copied().unwrap_or(0)));
+    }
+
+    fn record_cutoff(&self, _game: &PositionType, r#move: &MoveType, depth: usize, _ply: usize) {
+        *self.history.borrow_mut().entry(*r#move).or_insert(0) += depth * depth;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connect_four::{ConnectFourMove, ConnectFourMoveGenerator, ConnectFourMoveOrderer, ConnectFourPosition, Piece};
+    use crate::core::move_ordering::{ChainedMoveOrderer, HashMoveOrderer, HistoryMoveOrderer, KillerMoveOrderer, MoveOrderer, OrderingContext};
+    use crate::core::r#move::MoveGenerator;
+
+    fn columns(moves: &[ConnectFourMove]) -> Vec<usize> {
+        moves.iter().map(|r#move| r#move.column).collect()
+    }
+
+    fn red(column: usize) -> ConnectFourMove {
+        ConnectFourMove { piece: Piece::Red, column }
+    }
+
+    #[test]
+    pub fn test_hash_move_orderer() {
+        let position = ConnectFourPosition::new();
+        let mut moves = ConnectFourMoveGenerator {}.get_moves(&position);
+
+        HashMoveOrderer::new().order_moves(&position, &mut moves, &OrderingContext { ply: 0, hash_move: None });
+        assert_eq!(columns(&moves), vec![0, 1, 2, 3, 4, 5, 6]);
+
+        HashMoveOrderer::new().order_moves(&position, &mut moves, &OrderingContext { ply: 0, hash_move: Some(red(4)) });
+        assert_eq!(columns(&moves), vec![4, 0, 1, 2, 3, 5, 6]);
+    }
+
+    #[test]
+    pub fn test_killer_move_orderer() {
+        let position = ConnectFourPosition::new();
+        let killer_move_orderer = KillerMoveOrderer::new(2);
+        killer_move_orderer.record_cutoff(&position, &red(5), 3, 1);
+        killer_move_orderer.record_cutoff(&position, &red(2), 3, 1);
+        killer_move_orderer.record_cutoff(&position, &red(6), 3, 1);
+
+        // only the two most recent killers at the same ply count
+        let mut moves = ConnectFourMoveGenerator {}.get_moves(&position);
+        killer_move_orderer.order_moves(&position, &mut moves, &OrderingContext { ply: 1, hash_move: None });
+        assert_eq!(columns(&moves), vec![6, 2, 0, 1, 3, 4, 5]);
+
+        let mut moves = ConnectFourMoveGenerator {}.get_moves(&position);
+        killer_move_orderer.order_moves(&position, &mut moves, &OrderingContext { ply: 2, hash_move: None });
+        assert_eq!(columns(&moves), vec![0, 1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    pub fn test_history_move_orderer() {
+        let position = ConnectFourPosition::new();
+        let history_move_orderer = HistoryMoveOrderer::new();
+        history_move_orderer.record_cutoff(&position, &red(1), 1, 4);
+        history_move_orderer.record_cutoff(&position, &red(1), 1, 4);
+        history_move_orderer.record_cutoff(&position, &red(5), 2, 3);
+
+        let mut moves = ConnectFourMoveGenerator {}.get_moves(&position);
+        history_move_orderer.order_moves(&position, &mut moves, &OrderingContext { ply: 0, hash_move: None });
+        assert_eq!(columns(&moves), vec![5, 1, 0, 2, 3, 4, 6]);
+    }
+
+    #[test]
+    pub fn test_chained_move_orderer() {
+        let position = ConnectFourPosition::new();
+        let chained_move_orderer = ChainedMoveOrderer::new(vec![
+            Box::new(HashMoveOrderer::new()),
+            Box::new(ConnectFourMoveOrderer {})
+        ]);
+
+        let mut moves = ConnectFourMoveGenerator {}.get_moves(&position);
+        chained_move_orderer.order_moves(&position, &mut moves, &OrderingContext { ply: 0, hash_move: Some(red(0)) });
+        assert_eq!(columns(&moves), vec![0, 3, 2, 4, 1, 5, 6]);
+    }
+}
@@ -1,7 +1,11 @@
-use std::marker::PhantomData;
-use crate::core::game::{Position, Oracle};
-use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+use crate::core::game::{AlternatingGame, DeterministicGame, Game, Position, Oracle, UnmakePosition};
+use crate::core::move_ordering::{MoveOrderer, OrderingContext};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator, SideToMovePositionEvaluator};
 use crate::core::r#move::{Move, MoveGenerator};
+use crate::core::random::Random;
+use crate::core::transposition_table::{Bound, PositionHash, TranspositionEntry, TranspositionTable, EXHAUSTIVE_DEPTH};
 
 /**
  * A MoveStrategy is a strategy for choosing moves (it sounds tautological, but it's true).
@@ -10,8 +14,8 @@ use crate::core::r#move::{Move, MoveGenerator};
  * In more complex games, it may involve recursively trying moves multiple levels deep and picking the best move according to some algorithm -- e.g.,
  * Minimax algorithm.
  *
- * The MoveStrategy should be stateless. We'll work on adding support for stateful components (e.g., hashing of positions to prevent re-evaluating
- * them) later.
+ * The MoveStrategy should be stateless, with one exception: it may cache what it learns about positions (e.g., in a {@link TranspositionTable})
+ * so that it doesn't re-evaluate them when they're reached again by a different order of moves.
  *
  * The MoveStrategy works closely with the {@link MoveGenerator}, since MoveStrategy's rely on the MoveGenerator to produce the moves that should be
  * evaluated.
@@ -19,51 +23,176 @@ use crate::core::r#move::{Move, MoveGenerator};
 pub trait MoveStrategy<PositionType, MoveType>
     where PositionType: Position<PositionType, MoveType>,
           MoveType: Move {
-    fn choose_move(&self, game: &PositionType) -> MoveType;
+    /// Choose a move for the player to move in the position, or None if they have no moves, not even a pass.
+    fn choose_move(&self, game: &PositionType) -> Option<MoveType>;
+
+    /// Choose a move like {@link choose_move}, along with the evaluation the strategy's search gave it, for the
+    /// player to move. The evaluation is None for strategies that don't evaluate their moves.
+    fn choose_evaluated_move(&self, game: &PositionType) -> Option<(MoveType, Option<PositionEvaluation>)> {
+        self.choose_move(game).map(|r#move| (r#move, None))
+    }
+}
+
+/// An ObservationMoveStrategy chooses moves in games with hidden information, from what the player can see of the
+/// position rather than from the position itself. See {@link ObservationModel}.
+pub trait ObservationMoveStrategy<ObservationType, MoveType>
+    where MoveType: Move {
+    /// Choose a move for the observing player, or None if they have no moves, not even a pass.
+    fn choose_move(&self, observation: &ObservationType) -> Option<MoveType>;
+}
+
+/// A transposition table together with the function that hashes positions into it. Keeping the hash function here
+/// means only strategies that are actually given a table need their positions to implement {@link PositionHash}.
+struct TranspositionCache<PositionType, MoveType: Move> {
+    table: RefCell<TranspositionTable<MoveType>>,
+    hash: fn(&PositionType) -> u64
+}
+
+impl<PositionType, MoveType: Move> TranspositionCache<PositionType, MoveType> {
+    fn new(capacity: usize) -> TranspositionCache<PositionType, MoveType>
+        where PositionType: PositionHash {
+        TranspositionCache {
+            table: RefCell::new(TranspositionTable::new(capacity)),
+            hash: PositionType::position_hash
+        }
+    }
+
+    fn probe(&self, game: &PositionType) -> Option<TranspositionEntry<MoveType>> {
+        self.table.borrow().get((self.hash)(game)).copied()
+    }
+
+    fn store(&self, game: &PositionType, depth: usize, evaluation: PositionEvaluation, bound: Bound, best_move: Option<MoveType>) {
+        self.table.borrow_mut().store(TranspositionEntry {
+            hash: (self.hash)(game),
+            depth,
+            evaluation,
+            bound,
+            best_move
+        });
+    }
+}
+
+/// Get the position's moves, or its pass if it has no others, in the order the move orderer prefers if there is one.
+fn ordered_moves<PositionType, MoveType, MoveGeneratorType>(move_generator: &MoveGeneratorType, move_orderer: &Option<Box<dyn MoveOrderer<PositionType, MoveType>>>, game: &PositionType, ply: usize, hash_move: Option<MoveType>) -> Vec<MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          MoveGeneratorType: MoveGenerator<PositionType, MoveType> {
+    let mut moves = move_generator.get_moves_or_pass(game);
+    if let Some(move_orderer) = move_orderer {
+        move_orderer.order_moves(game, &mut moves, &OrderingContext { ply, hash_move });
+    }
+    moves
+}
+
+/// Narrow an alpha-beta window to the evaluations that a position with moves left can have: since every line from the
+/// position takes at least one more ply, it can't be won or lost any sooner than that.
+///
+/// Besides pruning lines that can't beat a quicker win found elsewhere, this keeps the window from collapsing when
+/// its bounds are passed down the tree with {@link PositionEvaluation#remove_ply}. Returns Err with the evaluation
+/// to return if no evaluation in the window is possible.
+fn narrow_window(alpha: PositionEvaluation, beta: PositionEvaluation) -> Result<(PositionEvaluation, PositionEvaluation), PositionEvaluation> {
+    let alpha = if alpha < PositionEvaluation::Losing(1) { PositionEvaluation::Losing(1) } else { alpha };
+    let beta = if beta > PositionEvaluation::Winning(1) { PositionEvaluation::Winning(1) } else { beta };
+    if alpha >= beta {
+        return Err(alpha);
+    }
+    Ok((alpha, beta))
+}
+
+/// A RandomMoveStrategy picks one of the legal moves at random, each as likely as the others. It's the weakest
+/// strategy there is, which makes it a useful baseline to measure other strategies against.
+pub struct RandomMoveStrategy<GameType>
+    where GameType: Game
+{
+    move_generator: GameType::MoveGenerator,
+    random: RefCell<Random>
+}
+
+impl<GameType> RandomMoveStrategy<GameType>
+    where GameType: Game
+{
+    pub fn new(move_generator: GameType::MoveGenerator) -> RandomMoveStrategy<GameType> {
+        RandomMoveStrategy {
+            move_generator,
+            random: RefCell::new(Random::new(0))
+        }
+    }
+
+    /// Create a strategy for the given game. It plays the same way for either player.
+    pub fn for_game(game: &GameType) -> RandomMoveStrategy<GameType> {
+        RandomMoveStrategy::new(game.move_generator())
+    }
+
+    /// Seed the generator that picks the moves. Defaults to 0.
+    pub fn with_seed(self, seed: u64) -> RandomMoveStrategy<GameType> {
+        self.random.replace(Random::new(seed));
+        self
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for RandomMoveStrategy<GameType>
+    where GameType: Game {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        let moves = self.move_generator.get_moves_or_pass(game);
+        match moves.is_empty() {
+            true => None,
+            false => Some(moves[self.random.borrow_mut().below(moves.len())])
+        }
+    }
 }
 
 /// A MaxMoveStrategy always picks the move that leads to the best-encountered position for the player.
 /// It's not a very good strategy, because it might also permit the opponent to force a terrible position for the player.
 /// But it's a good starter strategy for building out the game APIs without getting bogged down in algorithms like minimax.
-pub struct MaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
-    where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+pub struct MaxMoveStrategy<GameType>
+    where GameType: DeterministicGame
 {
-    phantom_game: PhantomData<PositionType>,
-    phantom_move: PhantomData<MoveType>,
-    position_evaluator: PositionEvaluatorType,
-    move_generator: MoveGeneratorType,
-    oracle: OracleType
+    position_evaluator: GameType::PositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    transposition_cache: Option<TranspositionCache<GameType::Position, GameType::Move>>
 }
 
-impl<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> MaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
-    where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+impl<GameType> MaxMoveStrategy<GameType>
+    where GameType: DeterministicGame
 {
-    pub fn new(position_evaluator: PositionEvaluatorType, move_generator: MoveGeneratorType, oracle: OracleType) -> MaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> {
+    pub fn new(position_evaluator: GameType::PositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> MaxMoveStrategy<GameType> {
         MaxMoveStrategy {
-            phantom_game: PhantomData,
-            phantom_move: PhantomData,
             position_evaluator,
             move_generator,
-            oracle
+            oracle,
+            transposition_cache: None
         }
     }
 
-    fn choose_move_recursive(&self, game: &PositionType) -> Option<(MoveType, PositionEvaluation)> {
+    /// Create a strategy for the given game that plays for the player with the given index.
+    pub fn for_game(game: &GameType, player: usize) -> MaxMoveStrategy<GameType> {
+        MaxMoveStrategy::new(game.position_evaluator(player), game.move_generator(), game.oracle())
+    }
+
+    /// Cache the evaluations of positions in a transposition table with room for `capacity` entries, so that
+    /// positions reached by more than one order of moves are only searched once. The table persists between moves.
+    pub fn with_transposition_table(mut self, capacity: usize) -> MaxMoveStrategy<GameType>
+        where GameType::Position: PositionHash {
+        self.transposition_cache = Some(TranspositionCache::new(capacity));
+        self
+    }
+
+    fn choose_move_recursive(&self, game: &GameType::Position) -> Option<(GameType::Move, PositionEvaluation)> {
         if self.oracle.is_terminal(game) {
             return None;
         }
+        // the max strategy always searches to the end of the game, so every result it stores is exhaustive
+        if let Some(entry) = self.transposition_cache.as_ref().and_then(|cache| cache.probe(game)).filter(|entry| entry.depth == EXHAUSTIVE_DEPTH) {
+            if let Some(best_move) = entry.best_move {
+                return Some((best_move, entry.evaluation));
+            }
+        }
 
-        let moves = self.move_generator.get_moves(&game);
-        let mut best_move = moves[0];
-        let mut best_position_evaluation = PositionEvaluation::Losing;
+        let moves = self.move_generator.get_moves_or_pass(game);
+        let mut best_move = *moves.first()?;
+        let mut best_position_evaluation = PositionEvaluation::Losing(0);
         for r#move in moves {
             let new_position = &game.apply(&r#move);
 
@@ -71,28 +200,28 @@ impl<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleTyp
             let new_position_evaluation = match recursive {
                 None => self.position_evaluator.evaluate(new_position),
                 Some((_, recursive_evaluation)) =>  recursive_evaluation
-            };
-            if new_position_evaluation > best_position_evaluation {
+            }.add_ply();
+            if new_position_evaluation.improves_on(&best_position_evaluation) {
                 best_position_evaluation = new_position_evaluation;
                 best_move = r#move;
             }
         }
+        if let Some(cache) = &self.transposition_cache {
+            cache.store(game, EXHAUSTIVE_DEPTH, best_position_evaluation, Bound::Exact, Some(best_move));
+        }
         Some((best_move, best_position_evaluation))
     }
 }
 
-impl<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> MoveStrategy<PositionType, MoveType>
-for MaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
-    where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType> {
-    fn choose_move(&self, game: &PositionType) -> MoveType {
-        match self.choose_move_recursive(game) {
-            Some((best_move, _)) => best_move,
-            None => panic!("Expected to be able to make a move!")
-        }
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for MaxMoveStrategy<GameType>
+    where GameType: DeterministicGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        self.choose_move_recursive(game).map(|(best_move, evaluation)| (best_move, Some(evaluation)))
     }
 }
 
@@ -100,75 +229,1182 @@ for MaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGenerator
 ///
 /// Put another way, it assumes that the opponent will always pick the best move for them, and it picks the best move
 /// it can under that assumption.
-pub struct MinimaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
-    where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+pub struct MinimaxMoveStrategy<GameType>
+    where GameType: AlternatingGame
 {
-    phantom_game: PhantomData<PositionType>,
-    phantom_move: PhantomData<MoveType>,
-    position_evaluator: PositionEvaluatorType,
-    move_generator: MoveGeneratorType,
-    oracle: OracleType
+    position_evaluator: GameType::PositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize,
+    transposition_cache: Option<TranspositionCache<GameType::Position, GameType::Move>>,
+    depth_limit_reached: Cell<bool>
 }
 
-impl<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> MinimaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
-    where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType>
+impl<GameType> MinimaxMoveStrategy<GameType>
+    where GameType: AlternatingGame
 {
-    pub fn new(position_evaluator: PositionEvaluatorType, move_generator: MoveGeneratorType, oracle: OracleType) -> MinimaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> {
+    pub fn new(position_evaluator: GameType::PositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> MinimaxMoveStrategy<GameType> {
         MinimaxMoveStrategy {
-            phantom_game: PhantomData,
-            phantom_move: PhantomData,
             position_evaluator,
             move_generator,
-            oracle
+            oracle,
+            max_depth: usize::MAX,
+            transposition_cache: None,
+            depth_limit_reached: Cell::new(false)
+        }
+    }
+
+    /// Create a strategy for the given game that plays for the player with the given index.
+    pub fn for_game(game: &GameType, player: usize) -> MinimaxMoveStrategy<GameType> {
+        MinimaxMoveStrategy::new(game.position_evaluator(player), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies. Positions at that depth are scored with the position evaluator's
+    /// estimate instead of being searched further. The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> MinimaxMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Cache the evaluations of positions in a transposition table with room for `capacity` entries, so that
+    /// positions reached by more than one order of moves are only searched once. The table persists between moves.
+    pub fn with_transposition_table(mut self, capacity: usize) -> MinimaxMoveStrategy<GameType>
+        where GameType::Position: PositionHash {
+        self.transposition_cache = Some(TranspositionCache::new(capacity));
+        self
+    }
+
+    /// The minimax evaluation of a position in which the position evaluator's player is to move. With no depth
+    /// limit, this is the game-theoretic value of the position: Winning, Losing or Draw.
+    pub fn evaluate(&self, game: &GameType::Position) -> PositionEvaluation {
+        match self.choose_move_recursive(game, self.max_depth, true) {
+            Some((_, position_evaluation)) => position_evaluation,
+            None => self.position_evaluator.evaluate(game)
         }
     }
 
-    fn choose_move_recursive(&self, game: &PositionType, maximizing_player: bool) -> Option<(MoveType, PositionEvaluation)> {
+    /// Returns None if the position is a leaf of the search -- because it's terminal, because the depth budget is
+    /// exhausted, or because the player to move has no moves -- in which case the caller should evaluate the position
+    /// directly.
+    fn choose_move_recursive(&self, game: &GameType::Position, depth: usize, maximizing_player: bool) -> Option<(GameType::Move, PositionEvaluation)> {
         if self.oracle.is_terminal(game) {
             return None;
         }
+        if depth == 0 {
+            self.depth_limit_reached.set(true);
+            return None;
+        }
+        if let Some(entry) = self.transposition_cache.as_ref().and_then(|cache| cache.probe(game)).filter(|entry| entry.depth >= depth) {
+            if let Some(best_move) = entry.best_move {
+                if entry.depth != EXHAUSTIVE_DEPTH {
+                    self.depth_limit_reached.set(true);
+                }
+                return Some((best_move, entry.evaluation));
+            }
+        }
+        // track whether this subtree on its own runs into the depth limit
+        let depth_limit_reached = self.depth_limit_reached.replace(false);
 
-        let moves = self.move_generator.get_moves(&game);
-        let mut best_move = moves[0];
-        let mut best_position_evaluation = match maximizing_player { true => PositionEvaluation::Losing, false => PositionEvaluation::Winning };
+        let moves = self.move_generator.get_moves_or_pass(game);
+        let mut best_move = *moves.first()?;
+        let mut best_position_evaluation = match maximizing_player { true => PositionEvaluation::Losing(0), false => PositionEvaluation::Winning(0) };
         for r#move in moves {
             let new_position = &game.apply(&r#move);
-            let recursive = self.choose_move_recursive(new_position, !maximizing_player);
+            let recursive = self.choose_move_recursive(new_position, depth - 1, !maximizing_player);
             let new_position_evaluation = match recursive {
                 None => self.position_evaluator.evaluate(new_position),
                 Some((_, recursive_evaluation)) => recursive_evaluation
+            }.add_ply();
+            let improves = match maximizing_player {
+                true => new_position_evaluation.improves_on(&best_position_evaluation),
+                false => (-new_position_evaluation).improves_on(&-best_position_evaluation)
             };
-            if maximizing_player && new_position_evaluation > best_position_evaluation {
+            if improves {
                 best_position_evaluation = new_position_evaluation;
                 best_move = r#move;
-            } else if !maximizing_player && new_position_evaluation < best_position_evaluation {
+            }
+        }
+
+        let exhaustive = !self.depth_limit_reached.get();
+        self.depth_limit_reached.set(depth_limit_reached || !exhaustive);
+        if let Some(cache) = &self.transposition_cache {
+            cache.store(game, if exhaustive { EXHAUSTIVE_DEPTH } else { depth }, best_position_evaluation, Bound::Exact, Some(best_move));
+        }
+        Some((best_move, best_position_evaluation))
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for MinimaxMoveStrategy<GameType>
+    where GameType: AlternatingGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        self.choose_move_recursive(game, self.max_depth.max(1), true).map(|(best_move, evaluation)| (best_move, Some(evaluation)))
+    }
+}
+
+/// An AlphaBetaMoveStrategy picks the same move as a {@link MinimaxMoveStrategy}, but skips subtrees that can't
+/// change the outcome of the search.
+///
+/// Alpha is the best evaluation the maximizing player is already assured of, and beta is the best evaluation the
+/// minimizing player is already assured of. Once a node's evaluation falls outside that window, its remaining
+/// siblings can't affect the choice at the root and are pruned.
+pub struct AlphaBetaMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    position_evaluator: GameType::PositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize,
+    transposition_cache: Option<TranspositionCache<GameType::Position, GameType::Move>>,
+    move_orderer: Option<Box<dyn MoveOrderer<GameType::Position, GameType::Move>>>,
+    searched_nodes: Cell<usize>,
+    pruned_nodes: Cell<usize>,
+    depth_limit_reached: Cell<bool>
+}
+
+impl<GameType> AlphaBetaMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    pub fn new(position_evaluator: GameType::PositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> AlphaBetaMoveStrategy<GameType> {
+        AlphaBetaMoveStrategy {
+            position_evaluator,
+            move_generator,
+            oracle,
+            max_depth: usize::MAX,
+            transposition_cache: None,
+            move_orderer: None,
+            searched_nodes: Cell::new(0),
+            pruned_nodes: Cell::new(0),
+            depth_limit_reached: Cell::new(false)
+        }
+    }
+
+    /// Create a strategy for the given game that plays for the player with the given index.
+    pub fn for_game(game: &GameType, player: usize) -> AlphaBetaMoveStrategy<GameType> {
+        AlphaBetaMoveStrategy::new(game.position_evaluator(player), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies. Positions at that depth are scored with the position evaluator's
+    /// estimate instead of being searched further. The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> AlphaBetaMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Cache the evaluations and evaluation bounds of positions in a transposition table with room for `capacity`
+    /// entries, so that positions reached by more than one order of moves are only searched once. The table persists
+    /// between moves.
+    pub fn with_transposition_table(mut self, capacity: usize) -> AlphaBetaMoveStrategy<GameType>
+        where GameType::Position: PositionHash {
+        self.transposition_cache = Some(TranspositionCache::new(capacity));
+        self
+    }
+
+    /// Search each position's moves in the order given by the move orderer. If the strategy has a transposition
+    /// table, the orderer is also told the best move from the position's previous search.
+    pub fn with_move_orderer(mut self, move_orderer: Box<dyn MoveOrderer<GameType::Position, GameType::Move>>) -> AlphaBetaMoveStrategy<GameType> {
+        self.move_orderer = Some(move_orderer);
+        self
+    }
+
+    /// The number of positions that the most recent call to `choose_move` visited, including the root.
+    pub fn searched_nodes(&self) -> usize {
+        self.searched_nodes.get()
+    }
+
+    /// The number of child positions that the most recent call to `choose_move` skipped without searching. Each
+    /// pruned node stands in for the whole subtree below it.
+    pub fn pruned_nodes(&self) -> usize {
+        self.pruned_nodes.get()
+    }
+
+    /// Search the position to the given depth. If the deadline passes before the search completes, the search is
+    /// abandoned and its partial result discarded. Returns None if the position has no moves to search.
+    fn search(&self, game: &GameType::Position, depth: usize, deadline: Option<Instant>) -> Result<Option<(GameType::Move, PositionEvaluation)>, SearchTimeout> {
+        self.searched_nodes.set(0);
+        self.pruned_nodes.set(0);
+        self.depth_limit_reached.set(false);
+        self.choose_move_recursive(game, depth.max(1), 0, true, PositionEvaluation::Losing(0), PositionEvaluation::Winning(0), deadline)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn choose_move_recursive(&self, game: &GameType::Position, depth: usize, ply: usize, maximizing_player: bool, mut alpha: PositionEvaluation, mut beta: PositionEvaluation, deadline: Option<Instant>) -> Result<Option<(GameType::Move, PositionEvaluation)>, SearchTimeout> {
+        self.searched_nodes.set(self.searched_nodes.get() + 1);
+        if self.oracle.is_terminal(game) {
+            return Ok(None);
+        }
+        if depth == 0 {
+            self.depth_limit_reached.set(true);
+            return Ok(None);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(SearchTimeout);
+        }
+        let cached_entry = self.transposition_cache.as_ref().and_then(|cache| cache.probe(game));
+        let moves = ordered_moves(&self.move_generator, &self.move_orderer, game, ply, cached_entry.and_then(|entry| entry.best_move));
+        let move_count = moves.len();
+        let mut best_move = match moves.first() {
+            Some(&first_move) => first_move,
+            None => return Ok(None)
+        };
+        // a bound only says how good the position is, not which move makes it so, and the root has to pick a move
+        if let Some(entry) = cached_entry.filter(|entry| entry.depth >= depth && (ply > 0 || entry.bound == Bound::Exact)) {
+            match entry.bound {
+                Bound::Exact => {
+                    alpha = entry.evaluation;
+                    beta = entry.evaluation;
+                },
+                Bound::Lower if entry.evaluation > alpha => alpha = entry.evaluation,
+                Bound::Upper if entry.evaluation < beta => beta = entry.evaluation,
+                _ => ()
+            }
+            if alpha >= beta {
+                if entry.depth != EXHAUSTIVE_DEPTH {
+                    self.depth_limit_reached.set(true);
+                }
+                return Ok(Some((entry.best_move.unwrap_or(best_move), entry.evaluation)));
+            }
+        }
+        (alpha, beta) = match narrow_window(alpha, beta) {
+            Ok(window) => window,
+            Err(position_evaluation) => return Ok(Some((best_move, position_evaluation)))
+        };
+        let (original_alpha, original_beta) = (alpha, beta);
+        // track whether this subtree on its own runs into the depth limit
+        let depth_limit_reached = self.depth_limit_reached.replace(false);
+
+        let mut best_position_evaluation = match maximizing_player { true => PositionEvaluation::Losing(0), false => PositionEvaluation::Winning(0) };
+        for (index, r#move) in moves.into_iter().enumerate() {
+            let new_position = &game.apply(&r#move);
+            let recursive = self.choose_move_recursive(new_position, depth - 1, ply + 1, !maximizing_player, alpha.remove_ply(), beta.remove_ply(), deadline)?;
+            let new_position_evaluation = match recursive {
+                None => self.position_evaluator.evaluate(new_position),
+                Some((_, recursive_evaluation)) => recursive_evaluation
+            }.add_ply();
+            if (maximizing_player && new_position_evaluation.improves_on(&best_position_evaluation))
+                || (!maximizing_player && (-new_position_evaluation).improves_on(&-best_position_evaluation)) {
                 best_position_evaluation = new_position_evaluation;
                 best_move = r#move;
             }
+
+            if maximizing_player && best_position_evaluation > alpha {
+                alpha = best_position_evaluation;
+            } else if !maximizing_player && best_position_evaluation < beta {
+                beta = best_position_evaluation;
+            }
+            if alpha >= beta {
+                if let Some(move_orderer) = &self.move_orderer {
+                    move_orderer.record_cutoff(game, &r#move, depth, ply);
+                }
+                self.pruned_nodes.set(self.pruned_nodes.get() + move_count - index - 1);
+                break;
+            }
         }
-        Some((best_move, best_position_evaluation))
+
+        let exhaustive = !self.depth_limit_reached.get();
+        self.depth_limit_reached.set(depth_limit_reached || !exhaustive);
+        if let Some(cache) = &self.transposition_cache {
+            let bound = if best_position_evaluation <= original_alpha {
+                Bound::Upper
+            } else if best_position_evaluation >= original_beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            // when every move fails low, the first one is no better than the others
+            let best_move = match bound {
+                Bound::Upper => None,
+                _ => Some(best_move)
+            };
+            cache.store(game, if exhaustive { EXHAUSTIVE_DEPTH } else { depth }, best_position_evaluation, bound, best_move);
+        }
+        Ok(Some((best_move, best_position_evaluation)))
     }
 }
 
-impl<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType> MoveStrategy<PositionType, MoveType>
-for MinimaxMoveStrategy<PositionType, MoveType, PositionEvaluatorType, MoveGeneratorType, OracleType>
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for AlphaBetaMoveStrategy<GameType>
+    where GameType: AlternatingGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        match self.search(game, self.max_depth, None) {
+            Ok(best) => best.map(|(best_move, evaluation)| (best_move, Some(evaluation))),
+            Err(SearchTimeout) => unreachable!("A search without a deadline can't time out")
+        }
+    }
+}
+
+/// Signals that a search ran past its deadline.
+struct SearchTimeout;
+
+/// An IterativeDeepeningMoveStrategy runs an alpha-beta search to depth 1, then 2, then 3, and so on, until its time
+/// budget runs out, and plays the best move from the deepest search it completed.
+///
+/// The depth 1 search always runs to completion, even if it overruns the budget, so that there's always a move to
+/// play. Deepening also stops early once a search reaches every terminal position, since searching deeper can't
+/// change the result.
+pub struct IterativeDeepeningMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    alpha_beta: AlphaBetaMoveStrategy<GameType>,
+    time_budget: Duration,
+    completed_depth: Cell<usize>
+}
+
+impl<GameType> IterativeDeepeningMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    pub fn new(position_evaluator: GameType::PositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle, time_budget: Duration) -> IterativeDeepeningMoveStrategy<GameType> {
+        IterativeDeepeningMoveStrategy {
+            alpha_beta: AlphaBetaMoveStrategy::new(position_evaluator, move_generator, oracle),
+            time_budget,
+            completed_depth: Cell::new(0)
+        }
+    }
+
+    /// Create a strategy for the given game that plays for the player with the given index.
+    pub fn for_game(game: &GameType, player: usize, time_budget: Duration) -> IterativeDeepeningMoveStrategy<GameType> {
+        IterativeDeepeningMoveStrategy::new(game.position_evaluator(player), game.move_generator(), game.oracle(), time_budget)
+    }
+
+    /// Cache the evaluations and evaluation bounds of positions in a transposition table with room for `capacity`
+    /// entries. Besides saving work within a search, this lets each iteration reuse what the shallower ones learned.
+    pub fn with_transposition_table(mut self, capacity: usize) -> IterativeDeepeningMoveStrategy<GameType>
+        where GameType::Position: PositionHash {
+        self.alpha_beta = self.alpha_beta.with_transposition_table(capacity);
+        self
+    }
+
+    /// Search each position's moves in the order given by the move orderer. Orderers that learn from cutoffs, and
+    /// the hash move from a transposition table, carry what each iteration learned into the next.
+    pub fn with_move_orderer(mut self, move_orderer: Box<dyn MoveOrderer<GameType::Position, GameType::Move>>) -> IterativeDeepeningMoveStrategy<GameType> {
+        self.alpha_beta = self.alpha_beta.with_move_orderer(move_orderer);
+        self
+    }
+
+    /// The depth of the deepest search that the most recent call to `choose_move` completed.
+    pub fn completed_depth(&self) -> usize {
+        self.completed_depth.get()
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for IterativeDeepeningMoveStrategy<GameType>
+    where GameType: AlternatingGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        let deadline = Instant::now() + self.time_budget;
+        let mut depth = 1;
+        let (mut best_move, mut best_position_evaluation) = match self.alpha_beta.search(game, depth, None) {
+            Ok(best) => best?,
+            Err(SearchTimeout) => unreachable!("A search without a deadline can't time out")
+        };
+
+        while self.alpha_beta.depth_limit_reached.get() {
+            match self.alpha_beta.search(game, depth + 1, Some(deadline)) {
+                Ok(Some((depth_best_move, depth_position_evaluation))) => {
+                    best_move = depth_best_move;
+                    best_position_evaluation = depth_position_evaluation;
+                    depth += 1;
+                },
+                Ok(None) | Err(SearchTimeout) => break
+            }
+        }
+
+        self.completed_depth.set(depth);
+        Some((best_move, Some(best_position_evaluation)))
+    }
+}
+
+/// How a search plays moves on the positions it explores. Searches are written once, generic over their MoveMaker,
+/// and {@link NegamaxMoveStrategy} picks the monomorphized search to run when it's built.
+trait MoveMaker<PositionType, MoveType>
     where PositionType: Position<PositionType, MoveType>,
-          MoveType: Move,
-          PositionEvaluatorType: PositionEvaluator<PositionType, MoveType>,
-          MoveGeneratorType: MoveGenerator<PositionType, MoveType>,
-          OracleType: Oracle<PositionType, MoveType> {
-    fn choose_move(&self, game: &PositionType) -> MoveType {
-        match self.choose_move_recursive(game, true) {
-            Some((best_move, _)) => best_move,
-            None => panic!("Expected to be able to make a move!")
+          MoveType: Move {
+    type Undo;
+
+    fn make_move(game: &mut PositionType, m: &MoveType) -> Self::Undo;
+
+    fn unmake_move(game: &mut PositionType, m: &MoveType, undo: Self::Undo);
+}
+
+/// Plays moves with `apply`, keeping a copy of the position to go back to.
+struct CopyMoves;
+
+impl<PositionType, MoveType> MoveMaker<PositionType, MoveType> for CopyMoves
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+    type Undo = PositionType;
+
+    fn make_move(game: &mut PositionType, m: &MoveType) -> PositionType {
+        let undo = *game;
+        *game = game.apply(m);
+        undo
+    }
+
+    fn unmake_move(game: &mut PositionType, _m: &MoveType, undo: PositionType) {
+        *game = undo;
+    }
+}
+
+/// Plays moves in place with {@link UnmakePosition}, so the search never copies the position.
+struct UnmakeMoves;
+
+impl<PositionType, MoveType> MoveMaker<PositionType, MoveType> for UnmakeMoves
+    where PositionType: Position<PositionType, MoveType> + UnmakePosition<MoveType>,
+          MoveType: Move {
+    type Undo = PositionType::Undo;
+
+    fn make_move(game: &mut PositionType, m: &MoveType) -> PositionType::Undo {
+        game.make_move(m)
+    }
+
+    fn unmake_move(game: &mut PositionType, m: &MoveType, undo: PositionType::Undo) {
+        game.unmake_move(m, undo);
+    }
+}
+
+/// The search a {@link NegamaxMoveStrategy} runs from the root, with the arguments of
+/// {@link NegamaxMoveStrategy#choose_move_recursive}.
+type NegamaxSearch<GameType> = fn(&NegamaxMoveStrategy<GameType>, &mut <GameType as Game>::Position, usize, usize, PositionEvaluation, PositionEvaluation) -> Option<(<GameType as Game>::Move, PositionEvaluation)>;
+
+/// A NegamaxMoveStrategy picks the same moves as an {@link AlphaBetaMoveStrategy}, but scores every position for the
+/// player to move in it rather than for a fixed player.
+///
+/// Since a position that's good for one player is equally bad for the other, every node of the search maximizes
+/// the negation of its children's evaluations, and there's no need to track whose turn it is. That also means a
+/// single {@link SideToMovePositionEvaluator} can be shared by both players' strategies.
+pub struct NegamaxMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    position_evaluator: GameType::SideToMovePositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize,
+    transposition_cache: Option<TranspositionCache<GameType::Position, GameType::Move>>,
+    move_orderer: Option<Box<dyn MoveOrderer<GameType::Position, GameType::Move>>>,
+    search: NegamaxSearch<GameType>,
+    depth_limit_reached: Cell<bool>
+}
+
+impl<GameType> NegamaxMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    pub fn new(position_evaluator: GameType::SideToMovePositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> NegamaxMoveStrategy<GameType> {
+        NegamaxMoveStrategy {
+            position_evaluator,
+            move_generator,
+            oracle,
+            max_depth: usize::MAX,
+            transposition_cache: None,
+            move_orderer: None,
+            search: NegamaxMoveStrategy::choose_move_recursive::<CopyMoves>,
+            depth_limit_reached: Cell::new(false)
+        }
+    }
+
+    /// Create a strategy for the given game. Evaluations are for the side to move, so the strategy can play for
+    /// either player.
+    pub fn for_game(game: &GameType) -> NegamaxMoveStrategy<GameType> {
+        NegamaxMoveStrategy::new(game.side_to_move_position_evaluator(), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies. Positions at that depth are scored with the position evaluator's
+    /// estimate instead of being searched further. The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> NegamaxMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Cache the evaluations and evaluation bounds of positions in a transposition table with room for `capacity`
+    /// entries, so that positions reached by more than one order of moves are only searched once. The table persists
+    /// between moves.
+    pub fn with_transposition_table(mut self, capacity: usize) -> NegamaxMoveStrategy<GameType>
+        where GameType::Position: PositionHash {
+        self.transposition_cache = Some(TranspositionCache::new(capacity));
+        self
+    }
+
+    /// Search each position's moves in the order given by the move orderer. If the strategy has a transposition
+    /// table, the orderer is also told the best move from the position's previous search.
+    pub fn with_move_orderer(mut self, move_orderer: Box<dyn MoveOrderer<GameType::Position, GameType::Move>>) -> NegamaxMoveStrategy<GameType> {
+        self.move_orderer = Some(move_orderer);
+        self
+    }
+
+    /// Search a single copy of the position, making each move on it and unmaking the move once it's searched,
+    /// instead of copying the position at every node. That matters little for small boards, but a lot for positions
+    /// that are expensive to copy.
+    pub fn with_make_unmake(mut self) -> NegamaxMoveStrategy<GameType>
+        where GameType::Position: UnmakePosition<GameType::Move> {
+        self.search = NegamaxMoveStrategy::choose_move_recursive::<UnmakeMoves>;
+        self
+    }
+
+    /// The moves at the root of a search, in the order the search would try them.
+    pub(crate) fn root_moves(&self, game: &GameType::Position) -> Vec<GameType::Move> {
+        if self.oracle.is_terminal(game) {
+            return vec![];
         }
+        let cached_entry = self.transposition_cache.as_ref().and_then(|cache| cache.probe(game));
+        ordered_moves(&self.move_generator, &self.move_orderer, game, 0, cached_entry.and_then(|entry| entry.best_move))
+    }
+
+    /// The evaluation of a move at the root of a search, for the player making it. The move is searched with the
+    /// full window, so the root moves can be searched independently of each other, e.g. on different threads.
+    pub(crate) fn evaluate_root_move(&self, game: &GameType::Position, r#move: &GameType::Move) -> PositionEvaluation {
+        let mut new_position = game.apply(r#move);
+        match (self.search)(self, &mut new_position, self.max_depth.max(1) - 1, 1, PositionEvaluation::Losing(0), PositionEvaluation::Winning(0)) {
+            None => -self.position_evaluator.evaluate_for_side_to_move(&new_position),
+            Some((_, recursive_evaluation)) => -recursive_evaluation
+        }.add_ply()
+    }
+
+    /// The evaluation of the position after the move, for the player making the move. The move is unmade before
+    /// returning, so `game` is left as it was.
+    fn evaluate_move<MoveMakerType>(&self, game: &mut GameType::Position, r#move: &GameType::Move, depth: usize, ply: usize, alpha: PositionEvaluation, beta: PositionEvaluation) -> PositionEvaluation
+        where MoveMakerType: MoveMaker<GameType::Position, GameType::Move> {
+        let undo = MoveMakerType::make_move(game, r#move);
+        let position_evaluation = match self.choose_move_recursive::<MoveMakerType>(game, depth - 1, ply + 1, -beta.remove_ply(), -alpha.remove_ply()) {
+            None => -self.position_evaluator.evaluate_for_side_to_move(game),
+            Some((_, recursive_evaluation)) => -recursive_evaluation
+        }.add_ply();
+        MoveMakerType::unmake_move(game, r#move, undo);
+        position_evaluation
+    }
+
+    /// Returns None if the position is a leaf of the search, in which case the caller should evaluate the position
+    /// directly. Evaluations are for the player to move in `game`, which is left as it was.
+    fn choose_move_recursive<MoveMakerType>(&self, game: &mut GameType::Position, depth: usize, ply: usize, mut alpha: PositionEvaluation, mut beta: PositionEvaluation) -> Option<(GameType::Move, PositionEvaluation)>
+        where MoveMakerType: MoveMaker<GameType::Position, GameType::Move> {
+        if self.oracle.is_terminal(game) {
+            return None;
+        }
+        if depth == 0 {
+            self.depth_limit_reached.set(true);
+            return None;
+        }
+
+        let cached_entry = self.transposition_cache.as_ref().and_then(|cache| cache.probe(game));
+        let moves = ordered_moves(&self.move_generator, &self.move_orderer, game, ply, cached_entry.and_then(|entry| entry.best_move));
+        let mut best_move = *moves.first()?;
+        // a bound only says how good the position is, not which move makes it so, and the root has to pick a move
+        if let Some(entry) = cached_entry.filter(|entry| entry.depth >= depth && (ply > 0 || entry.bound == Bound::Exact)) {
+            match entry.bound {
+                Bound::Exact => {
+                    alpha = entry.evaluation;
+                    beta = entry.evaluation;
+                },
+                Bound::Lower if entry.evaluation > alpha => alpha = entry.evaluation,
+                Bound::Upper if entry.evaluation < beta => beta = entry.evaluation,
+                _ => ()
+            }
+            if alpha >= beta {
+                if entry.depth != EXHAUSTIVE_DEPTH {
+                    self.depth_limit_reached.set(true);
+                }
+                return Some((entry.best_move.unwrap_or(best_move), entry.evaluation));
+            }
+        }
+        (alpha, beta) = match narrow_window(alpha, beta) {
+            Ok(window) => window,
+            Err(position_evaluation) => return Some((best_move, position_evaluation))
+        };
+        let (original_alpha, original_beta) = (alpha, beta);
+        // track whether this subtree on its own runs into the depth limit
+        let depth_limit_reached = self.depth_limit_reached.replace(false);
+
+        let mut best_position_evaluation = PositionEvaluation::Losing(0);
+        for r#move in moves {
+            let new_position_evaluation = self.evaluate_move::<MoveMakerType>(game, &r#move, depth, ply, alpha, beta);
+            if new_position_evaluation.improves_on(&best_position_evaluation) {
+                best_position_evaluation = new_position_evaluation;
+                best_move = r#move;
+            }
+            if best_position_evaluation > alpha {
+                alpha = best_position_evaluation;
+            }
+            if alpha >= beta {
+                if let Some(move_orderer) = &self.move_orderer {
+                    move_orderer.record_cutoff(game, &r#move, depth, ply);
+                }
+                break;
+            }
+        }
+
+        let exhaustive = !self.depth_limit_reached.get();
+        self.depth_limit_reached.set(depth_limit_reached || !exhaustive);
+        if let Some(cache) = &self.transposition_cache {
+            let bound = if best_position_evaluation <= original_alpha {
+                Bound::Upper
+            } else if best_position_evaluation >= original_beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            // when every move fails low, the first one is no better than the others
+            let best_move = match bound {
+                Bound::Upper => None,
+                _ => Some(best_move)
+            };
+            cache.store(game, if exhaustive { EXHAUSTIVE_DEPTH } else { depth }, best_position_evaluation, bound, best_move);
+        }
+        Some((best_move, best_position_evaluation))
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for NegamaxMoveStrategy<GameType>
+    where GameType: AlternatingGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        let mut game = *game;
+        (self.search)(self, &mut game, self.max_depth.max(1), 0, PositionEvaluation::Losing(0), PositionEvaluation::Winning(0)).map(|(best_move, evaluation)| (best_move, Some(evaluation)))
+    }
+}
+
+/// A PrincipalVariationMoveStrategy (also known as NegaScout) picks the same moves as a {@link NegamaxMoveStrategy},
+/// but bets that the first move it searches at each position is the best one.
+///
+/// The first move is searched with the full alpha-beta window. Every other move is only searched with a null window,
+/// which is much cheaper and only tells whether the move is better than the best so far. When it is, the null-window
+/// search is said to fail high, and the move is searched again with the full window to find out by how much. The
+/// better the moves are ordered, the less often that happens.
+pub struct PrincipalVariationMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    position_evaluator: GameType::SideToMovePositionEvaluator,
+    move_generator: GameType::MoveGenerator,
+    oracle: GameType::Oracle,
+    max_depth: usize,
+    move_orderer: Option<Box<dyn MoveOrderer<GameType::Position, GameType::Move>>>,
+    re_searches: Cell<usize>
+}
+
+impl<GameType> PrincipalVariationMoveStrategy<GameType>
+    where GameType: AlternatingGame
+{
+    pub fn new(position_evaluator: GameType::SideToMovePositionEvaluator, move_generator: GameType::MoveGenerator, oracle: GameType::Oracle) -> PrincipalVariationMoveStrategy<GameType> {
+        PrincipalVariationMoveStrategy {
+            position_evaluator,
+            move_generator,
+            oracle,
+            max_depth: usize::MAX,
+            move_orderer: None,
+            re_searches: Cell::new(0)
+        }
+    }
+
+    /// Create a strategy for the given game. Evaluations are for the side to move, so the strategy can play for
+    /// either player.
+    pub fn for_game(game: &GameType) -> PrincipalVariationMoveStrategy<GameType> {
+        PrincipalVariationMoveStrategy::new(game.side_to_move_position_evaluator(), game.move_generator(), game.oracle())
+    }
+
+    /// Limit the search to `max_depth` plies. Positions at that depth are scored with the position evaluator's
+    /// estimate instead of being searched further. The root is always searched at least one ply deep.
+    pub fn with_max_depth(mut self, max_depth: usize) -> PrincipalVariationMoveStrategy<GameType> {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Search each position's moves in the order given by the move orderer.
+    pub fn with_move_orderer(mut self, move_orderer: Box<dyn MoveOrderer<GameType::Position, GameType::Move>>) -> PrincipalVariationMoveStrategy<GameType> {
+        self.move_orderer = Some(move_orderer);
+        self
+    }
+
+    /// The number of null-window searches that failed high and had to be repeated with a full window during the most
+    /// recent call to `choose_move`.
+    pub fn re_searches(&self) -> usize {
+        self.re_searches.get()
+    }
+
+    /// The evaluation of the position after the move, for the player making the move.
+    fn evaluate_move(&self, game: &GameType::Position, r#move: &GameType::Move, depth: usize, ply: usize, alpha: PositionEvaluation, beta: PositionEvaluation) -> PositionEvaluation {
+        let new_position = &game.apply(r#move);
+        match self.choose_move_recursive(new_position, depth - 1, ply + 1, -beta.remove_ply(), -alpha.remove_ply()) {
+            None => -self.position_evaluator.evaluate_for_side_to_move(new_position),
+            Some((_, recursive_evaluation)) => -recursive_evaluation
+        }.add_ply()
+    }
+
+    /// Returns None if the position is a leaf of the search, in which case the caller should evaluate the position
+    /// directly. Evaluations are for the player to move in `game`.
+    fn choose_move_recursive(&self, game: &GameType::Position, depth: usize, ply: usize, alpha: PositionEvaluation, beta: PositionEvaluation) -> Option<(GameType::Move, PositionEvaluation)> {
+        if depth == 0 || self.oracle.is_terminal(game) {
+            return None;
+        }
+
+        let moves = ordered_moves(&self.move_generator, &self.move_orderer, game, ply, None);
+        let mut best_move = *moves.first()?;
+        let (mut alpha, beta) = match narrow_window(alpha, beta) {
+            Ok(window) => window,
+            Err(position_evaluation) => return Some((best_move, position_evaluation))
+        };
+        let mut best_position_evaluation = PositionEvaluation::Losing(0);
+        for (index, r#move) in moves.into_iter().enumerate() {
+            let new_position_evaluation = match (index, alpha.successor()) {
+                (0, _) => self.evaluate_move(game, &r#move, depth, ply, alpha, beta),
+                // nothing beats alpha, so there's nothing to test for
+                (_, None) => break,
+                (_, Some(null_beta)) => {
+                    let null_window_evaluation = self.evaluate_move(game, &r#move, depth, ply, alpha, null_beta);
+                    if null_window_evaluation > alpha && null_window_evaluation < beta {
+                        self.re_searches.set(self.re_searches.get() + 1);
+                        self.evaluate_move(game, &r#move, depth, ply, alpha, beta)
+                    } else {
+                        null_window_evaluation
+                    }
+                }
+            };
+            if new_position_evaluation.improves_on(&best_position_evaluation) {
+                best_position_evaluation = new_position_evaluation;
+                best_move = r#move;
+            }
+            if best_position_evaluation > alpha {
+                alpha = best_position_evaluation;
+            }
+            if alpha >= beta {
+                if let Some(move_orderer) = &self.move_orderer {
+                    move_orderer.record_cutoff(game, &r#move, depth, ply);
+                }
+                break;
+            }
+        }
+        Some((best_move, best_position_evaluation))
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for PrincipalVariationMoveStrategy<GameType>
+    where GameType: AlternatingGame {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
     }
-}
\ No newline at end of file
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        self.re_searches.set(0);
+        self.choose_move_recursive(game, self.max_depth.max(1), 0, PositionEvaluation::Losing(0), PositionEvaluation::Winning(0)).map(|(best_move, evaluation)| (best_move, Some(evaluation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{Game, GameOutcome, Oracle, Position};
+    use std::time::{Duration, Instant};
+    use crate::core::move_strategy::{AlphaBetaMoveStrategy, CopyMoves, IterativeDeepeningMoveStrategy, MinimaxMoveStrategy, MoveStrategy, NegamaxMoveStrategy, PrincipalVariationMoveStrategy};
+    use crate::core::position_evaluator::PositionEvaluation;
+    use crate::core::r#move::MoveGenerator;
+    use crate::core::test_support::{TreeGame, TreeNode};
+    use crate::core::transposition_table::{Bound, PositionHash, TranspositionEntry, EXHAUSTIVE_DEPTH};
+    use crate::connect_four::{ConnectFour, ConnectFourMoveGenerator, ConnectFourMoveOrderer, ConnectFourOracle, ConnectFourPosition, ConnectFourPositionEvaluator, ConnectFourSideToMovePositionEvaluator};
+    use crate::connect_four::Piece as ConnectFourPiece;
+    use crate::tictactoe::{Piece, TicTacToe, TicTacToeMove, TicTacToeMoveGenerator, TicTacToeOracle, TicTacToePosition, TicTacToePositionEvaluator, TicTacToeSideToMovePositionEvaluator};
+
+    #[test]
+    pub fn test_alpha_beta_matches_minimax() {
+        let minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let alpha_beta = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        // every position where X is to move after each side has played once
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(minimax.choose_move(&game).unwrap().position, alpha_beta.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_alpha_beta_prunes_nodes() {
+        let alpha_beta = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+
+        //  X |   |
+        // -----------
+        //    | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), None, None, None, Some(Piece::O), None, None, None, None], 1);
+
+        alpha_beta.choose_move(&game);
+        assert!(alpha_beta.pruned_nodes() > 0);
+    }
+
+    #[test]
+    pub fn test_minimax_reports_a_forced_draw() {
+        let minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        assert!(matches!(minimax.evaluate(&TicTacToePosition::new()), PositionEvaluation::Draw));
+
+        //  X |   |
+        // -----------
+        //    | O |
+        // -----------
+        //    |   |
+        // X to move can't force a win, but a depth-limited search can't prove it
+        let game = TicTacToePosition::from_board([Some(Piece::X), None, None, None, Some(Piece::O), None, None, None, None], 1);
+        assert!(matches!(minimax.evaluate(&game), PositionEvaluation::Draw));
+        let depth_limited = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_max_depth(2);
+        assert!(matches!(depth_limited.evaluate(&game), PositionEvaluation::Estimate(_)));
+
+        //  X | X |
+        // -----------
+        //  O | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), None, Some(Piece::O), Some(Piece::O), None, None, None, None], 1);
+        assert_eq!(minimax.evaluate(&game), PositionEvaluation::Winning(1));
+    }
+
+    #[test]
+    pub fn test_strategies_prefer_the_quickest_win() {
+        //  X | X | O
+        // -----------
+        //    |   | O
+        // -----------
+        //    | O | X
+        // X wins at once in the center, but the middle left also wins, more slowly, and comes first
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), Some(Piece::O), None, None, Some(Piece::O), None, Some(Piece::O), Some(Piece::X)], 1);
+        let minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        assert_eq!(minimax.evaluate(&game).to_string(), "mate in 1");
+        assert_eq!(minimax.choose_move(&game).unwrap().position, 4);
+
+        let alpha_beta = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        assert_eq!(alpha_beta.choose_move(&game).unwrap().position, 4);
+        let negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        assert_eq!(negamax.choose_move(&game).unwrap().position, 4);
+        let principal_variation = PrincipalVariationMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        assert_eq!(principal_variation.choose_move(&game).unwrap().position, 4);
+    }
+
+    #[test]
+    pub fn test_strategies_dont_mistake_an_even_estimate_for_a_draw() {
+        // a proven draw, and a position that only looks even, at the same node
+        let game = TreeGame { nodes: &[
+            TreeNode::Branch(&[1, 2], 0.0),
+            TreeNode::Terminal(GameOutcome::Draw),
+            TreeNode::Branch(&[3], 0.0),
+            TreeNode::Terminal(GameOutcome::Win(0))
+        ] };
+        let mut start = game.initial_position();
+
+        let negamax = NegamaxMoveStrategy::for_game(&game).with_max_depth(1);
+        let (best_move, position_evaluation) = negamax.choose_move_recursive::<CopyMoves>(&mut start, 1, 0, PositionEvaluation::Losing(0), PositionEvaluation::Winning(0)).unwrap();
+        assert_eq!(best_move.node, 2);
+        assert!(matches!(position_evaluation, PositionEvaluation::Estimate(_)));
+
+        assert_eq!(MinimaxMoveStrategy::for_game(&game, 0).with_max_depth(1).choose_move(&start).unwrap().node, 2);
+        assert_eq!(AlphaBetaMoveStrategy::for_game(&game, 0).with_max_depth(1).choose_move(&start).unwrap().node, 2);
+        assert_eq!(PrincipalVariationMoveStrategy::for_game(&game).with_max_depth(1).choose_move(&start).unwrap().node, 2);
+    }
+
+    #[test]
+    pub fn test_depth_limited_minimax_uses_estimates() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let minimax = MinimaxMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(1);
+
+        // the center column takes part in the most windows of four, so it's the best opening by estimate alone
+        assert_eq!(minimax.choose_move(&ConnectFourPosition::new()).unwrap().column, 3);
+    }
+
+    #[test]
+    pub fn test_depth_limited_alpha_beta_matches_minimax() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let minimax = MinimaxMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(3);
+        let alpha_beta = AlphaBetaMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(3);
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        for red_move in move_generator.get_moves(&start) {
+            let after_red = start.apply(&red_move);
+            for black_move in move_generator.get_moves(&after_red) {
+                let game = after_red.apply(&black_move);
+                assert_eq!(minimax.choose_move(&game).unwrap().column, alpha_beta.choose_move(&game).unwrap().column);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_move_ordering_preserves_alpha_beta_moves_and_searches_fewer_nodes() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let alpha_beta = AlphaBetaMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(5);
+        let ordered_alpha_beta = AlphaBetaMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(5)
+            .with_move_orderer(Box::new(ConnectFourMoveOrderer {}));
+
+        // searching the center columns first finds the cutoffs sooner, without changing the move that's found
+        let game = ConnectFourPosition::new();
+        assert_eq!(ordered_alpha_beta.choose_move(&game).unwrap().column, alpha_beta.choose_move(&game).unwrap().column);
+        assert!(ordered_alpha_beta.searched_nodes() < alpha_beta.searched_nodes());
+    }
+
+    #[test]
+    pub fn test_iterative_deepening_finishes_small_games() {
+        let minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let iterative_deepening = IterativeDeepeningMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {}, Duration::from_secs(60));
+
+        //  X |   |
+        // -----------
+        //    | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), None, None, None, Some(Piece::O), None, None, None, None], 1);
+
+        // the whole tree fits in the budget, so the search stops at the last ply on its own
+        assert_eq!(iterative_deepening.choose_move(&game).unwrap().position, minimax.choose_move(&game).unwrap().position);
+        assert_eq!(iterative_deepening.completed_depth(), 7);
+    }
+
+    #[test]
+    pub fn test_iterative_deepening_respects_time_budget() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let iterative_deepening = IterativeDeepeningMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle(), Duration::from_millis(100));
+
+        let start = Instant::now();
+        iterative_deepening.choose_move(&ConnectFourPosition::new());
+        assert!(start.elapsed() < Duration::from_secs(2));
+        assert!(iterative_deepening.completed_depth() >= 1);
+    }
+
+    #[test]
+    pub fn test_transposition_table_preserves_minimax_moves() {
+        let minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let cached_minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_transposition_table(1 << 12);
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(minimax.choose_move(&game).unwrap().position, cached_minimax.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_cached_alpha_beta_plays_tic_tac_toe_to_a_draw() {
+        let x_player = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_transposition_table(1 << 12);
+        let o_player = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::O }, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_transposition_table(1 << 12);
+        let oracle = TicTacToeOracle {};
+
+        let mut game = TicTacToePosition::new();
+        while !oracle.is_terminal(&game) {
+            let next_move = match oracle.next_player(&game) {
+                Some(0) => x_player.choose_move(&game),
+                _ => o_player.choose_move(&game)
+            };
+            game = game.apply(&next_move.unwrap());
+        }
+        assert_eq!(oracle.outcome(&game), Some(GameOutcome::Draw));
+    }
+
+    #[test]
+    pub fn test_alpha_beta_ignores_bounds_at_the_root() {
+        let alpha_beta = AlphaBetaMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_transposition_table(1 << 12);
+
+        //  X | X |
+        // -----------
+        //  O | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), None, Some(Piece::O), Some(Piece::O), None, None, None, None], 1);
+        // an upper bound low enough to cut off any search, left over from a search that found nothing good
+        alpha_beta.transposition_cache.as_ref().unwrap().table.borrow_mut().store(TranspositionEntry {
+            hash: game.position_hash(),
+            depth: EXHAUSTIVE_DEPTH,
+            evaluation: PositionEvaluation::Losing(0),
+            bound: Bound::Upper,
+            best_move: Some(TicTacToeMove { position: 8, piece: Piece::X })
+        });
+
+        assert_eq!(alpha_beta.choose_move(&game).unwrap().position, 2);
+    }
+
+    #[test]
+    pub fn test_negamax_matches_minimax_for_both_players() {
+        let negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let x_minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::X }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let o_minimax = MinimaxMoveStrategy::<TicTacToe>::new(TicTacToePositionEvaluator { player_piece: Piece::O }, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            assert_eq!(negamax.choose_move(&after_x).unwrap().position, o_minimax.choose_move(&after_x).unwrap().position);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(negamax.choose_move(&game).unwrap().position, x_minimax.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_transposition_table_preserves_negamax_moves() {
+        let negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let cached_negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_transposition_table(1 << 12);
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            assert_eq!(negamax.choose_move(&after_x).unwrap().position, cached_negamax.choose_move(&after_x).unwrap().position);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(negamax.choose_move(&game).unwrap().position, cached_negamax.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_negamax_stores_exhaustive_searches_as_exhaustive() {
+        let negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_max_depth(8)
+            .with_transposition_table(1 << 12);
+        let shallow_negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {})
+            .with_max_depth(2)
+            .with_transposition_table(1 << 12);
+
+        //    |   |
+        // ---+---+---
+        //    | X |
+        // ---+---+---
+        //    |   |
+        let game = TicTacToePosition::new().apply(&TicTacToeMove { position: 4, piece: Piece::X });
+        negamax.choose_move(&game);
+        shallow_negamax.choose_move(&game);
+
+        // eight moves are left, so eight plies reach the end of every line of play, but two don't
+        assert_eq!(negamax.transposition_cache.as_ref().unwrap().probe(&game).unwrap().depth, EXHAUSTIVE_DEPTH);
+        assert_eq!(shallow_negamax.transposition_cache.as_ref().unwrap().probe(&game).unwrap().depth, 2);
+    }
+
+    #[test]
+    pub fn test_depth_limited_negamax_matches_minimax() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let minimax = MinimaxMoveStrategy::<ConnectFour>::new(ConnectFourPositionEvaluator { player_piece: ConnectFourPiece::Red }, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(3);
+        let negamax = NegamaxMoveStrategy::<ConnectFour>::new(ConnectFourSideToMovePositionEvaluator {}, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(3);
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        for red_move in move_generator.get_moves(&start) {
+            let after_red = start.apply(&red_move);
+            for black_move in move_generator.get_moves(&after_red) {
+                let game = after_red.apply(&black_move);
+                assert_eq!(minimax.choose_move(&game).unwrap().column, negamax.choose_move(&game).unwrap().column);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_principal_variation_matches_negamax() {
+        let negamax = NegamaxMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let principal_variation = PrincipalVariationMoveStrategy::<TicTacToe>::new(TicTacToeSideToMovePositionEvaluator {}, TicTacToeMoveGenerator {}, TicTacToeOracle {});
+        let move_generator = TicTacToeMoveGenerator {};
+        let oracle = TicTacToeOracle {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let after_x = start.apply(&x_move);
+            for o_move in move_generator.get_moves(&after_x) {
+                let game = after_x.apply(&o_move);
+                if oracle.is_terminal(&game) {
+                    continue;
+                }
+                assert_eq!(negamax.choose_move(&game).unwrap().position, principal_variation.choose_move(&game).unwrap().position);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_depth_limited_principal_variation_matches_negamax() {
+        let connect_four_oracle = || ConnectFourOracle{position_evaluator: ConnectFourPositionEvaluator{player_piece: ConnectFourPiece::Phantom}};
+        let evaluator = ConnectFourSideToMovePositionEvaluator {};
+        let negamax = NegamaxMoveStrategy::<ConnectFour>::new(evaluator, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(4);
+        let principal_variation = PrincipalVariationMoveStrategy::<ConnectFour>::new(evaluator, ConnectFourMoveGenerator {}, connect_four_oracle())
+            .with_max_depth(4);
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        let mut re_searches = 0;
+        for red_move in move_generator.get_moves(&start) {
+            let game = start.apply(&red_move);
+            assert_eq!(negamax.choose_move(&game).unwrap().column, principal_variation.choose_move(&game).unwrap().column);
+            re_searches += principal_variation.re_searches();
+        }
+        // columns are generated left to right, which is far from best first, so some null windows must fail high
+        assert!(re_searches > 0);
+    }
+
+    #[test]
+    pub fn test_make_unmake_negamax_matches_negamax() {
+        let negamax = NegamaxMoveStrategy::for_game(&TicTacToe {});
+        let in_place = NegamaxMoveStrategy::for_game(&TicTacToe {})
+            .with_make_unmake();
+        let move_generator = TicTacToeMoveGenerator {};
+
+        let start = TicTacToePosition::new();
+        for x_move in move_generator.get_moves(&start) {
+            let game = start.apply(&x_move);
+            assert_eq!(negamax.choose_move(&game).unwrap().position, in_place.choose_move(&game).unwrap().position);
+        }
+
+        let negamax = NegamaxMoveStrategy::for_game(&ConnectFour {})
+            .with_max_depth(5);
+        let in_place = NegamaxMoveStrategy::for_game(&ConnectFour {})
+            .with_max_depth(5)
+            .with_make_unmake();
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        for red_move in move_generator.get_moves(&start) {
+            let game = start.apply(&red_move);
+            assert_eq!(negamax.choose_move(&game).unwrap().column, in_place.choose_move(&game).unwrap().column);
+        }
+    }
+}
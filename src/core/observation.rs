@@ -0,0 +1,38 @@
+use std::fmt::Debug;
+use crate::core::game::{Game, Position};
+use crate::core::r#move::Move;
+use crate::core::random::Random;
+
+/// An ObservationModel describes what each player of a game with hidden information can see, like their own hand of
+/// cards but not anyone else's.
+///
+/// A player's observation of a position stands for every position the player can't tell apart from it, which is
+/// known as the player's information set. Strategies can't search an information set directly, so the model can also
+/// pick one of its positions at random for them to search, which is known as determinization.
+pub trait ObservationModel<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    /// What a player sees of a position. It should say which player is observing, so that the observation alone is
+    /// enough to determinize.
+    type Observation: Copy + Debug;
+
+    /// What the player with the given index can see of the position.
+    fn observe(&self, position: &PositionType, player: usize) -> Self::Observation;
+
+    /// Pick, at random, a position the observing player can't tell apart from the one they observed. Every position
+    /// it picks should produce the same observation, and the closer the odds are to the real odds of each position,
+    /// the better strategies will play.
+    fn determinize(&self, observation: &Self::Observation, random: &mut Random) -> PositionType;
+}
+
+/// A Game in which players can't see all of the position. Players are handed observations instead, which the
+/// {@link GameEngine} makes with the game's observation model.
+pub trait ImperfectInformationGame: Game {
+    type ObservationModel: ObservationModel<Self::Position, Self::Move>;
+
+    fn observation_model(&self) -> Self::ObservationModel;
+}
+
+/// The observation type of an {@link ImperfectInformationGame}.
+pub type ObservationFor<GameType> = <<GameType as ImperfectInformationGame>::ObservationModel as ObservationModel<<GameType as Game>::Position, <GameType as Game>::Move>>::Observation;
@@ -0,0 +1,255 @@
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+use crate::core::game::{GameOutcome, Position};
+use crate::core::r#move::Move;
+use crate::core::record::{GameRecord, RecordedMove};
+
+/// A GameObserver is told about everything that happens in a game the {@link GameEngine} plays, for printing,
+/// logging or checking it. Any number of observers can watch one engine, and each callback does nothing unless the
+/// observer overrides it.
+pub trait GameObserver<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    /// Called once, before the first move, with the position the game starts from.
+    fn game_started(&mut self, _position: &PositionType) {}
+
+    /// Called after every move, including chance events, before the position it leads to is reported.
+    fn move_played(&mut self, _recorded_move: &RecordedMove<MoveType>) {}
+
+    /// Called with the position after every move.
+    fn position_changed(&mut self, _position: &PositionType) {}
+
+    /// Called once the game is over, with its record. Games the engine stops with an error never get here.
+    fn game_over(&mut self, _record: &GameRecord<PositionType, MoveType>) {}
+}
+
+/// A shared observer can be handed to the engine while the caller keeps a handle on it, to look at what it saw once
+/// the game is over.
+impl<PositionType, MoveType, ObserverType> GameObserver<PositionType, MoveType> for Rc<RefCell<ObserverType>>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          ObserverType: GameObserver<PositionType, MoveType>
+{
+    fn game_started(&mut self, position: &PositionType) {
+        self.borrow_mut().game_started(position)
+    }
+
+    fn move_played(&mut self, recorded_move: &RecordedMove<MoveType>) {
+        self.borrow_mut().move_played(recorded_move)
+    }
+
+    fn position_changed(&mut self, position: &PositionType) {
+        self.borrow_mut().position_changed(position)
+    }
+
+    fn game_over(&mut self, record: &GameRecord<PositionType, MoveType>) {
+        self.borrow_mut().game_over(record)
+    }
+}
+
+/// Prints every position to the console as the game goes.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ConsolePrinter {}
+
+impl<PositionType, MoveType> GameObserver<PositionType, MoveType> for ConsolePrinter
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    fn position_changed(&mut self, position: &PositionType) {
+        println!("{:?}", position);
+    }
+}
+
+/// Writes a line for every event in the game, to a file or any other writer.
+///
+/// The writer is only flushed when the game is over, so wrap files in a `BufWriter`, as {@link create} does. The
+/// callbacks can't fail, so a logger that can't write stops logging, and keeps the error for {@link error}.
+pub struct FileLogger<WriterType>
+    where WriterType: Write
+{
+    writer: WriterType,
+    error: Option<std::io::Error>
+}
+
+impl FileLogger<BufWriter<File>> {
+    /// Log to a new file at the given path, replacing any file already there.
+    pub fn create<PathType: AsRef<Path>>(path: PathType) -> std::io::Result<FileLogger<BufWriter<File>>> {
+        Ok(FileLogger::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<WriterType> FileLogger<WriterType>
+    where WriterType: Write
+{
+    pub fn new(writer: WriterType) -> FileLogger<WriterType> {
+        FileLogger { writer, error: None }
+    }
+
+    /// The error that stopped the logger, if writing failed.
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    pub fn into_inner(self) -> WriterType {
+        self.writer
+    }
+
+    fn log(&mut self, line: String) {
+        if self.error.is_none() {
+            self.error = writeln!(self.writer, "{}", line).err();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.error.is_none() {
+            self.error = self.writer.flush().err();
+        }
+    }
+}
+
+impl<PositionType, MoveType, WriterType> GameObserver<PositionType, MoveType> for FileLogger<WriterType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          WriterType: Write
+{
+    fn game_started(&mut self, position: &PositionType) {
+        self.log(format!("game started: {:?}", position));
+    }
+
+    fn move_played(&mut self, recorded_move: &RecordedMove<MoveType>) {
+        let line = match recorded_move.player {
+            Some(player) => format!("player {} played {:?} in {:?}, evaluation {:?}", player, recorded_move.r#move, recorded_move.time_taken, recorded_move.evaluation),
+            None => format!("chance played {:?}", recorded_move.r#move)
+        };
+        self.log(line);
+    }
+
+    fn position_changed(&mut self, position: &PositionType) {
+        self.log(format!("{:?}", position));
+    }
+
+    fn game_over(&mut self, record: &GameRecord<PositionType, MoveType>) {
+        self.log(format!("game over after {} moves: {:?}", record.moves.len(), record.outcome));
+        self.flush();
+    }
+}
+
+/// One of the callbacks a {@link GameObserver} receives.
+#[derive(Clone, Debug)]
+pub enum GameEvent<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    GameStarted(PositionType),
+    MovePlayed(RecordedMove<MoveType>),
+    PositionChanged(PositionType),
+    GameOver(Option<GameOutcome>)
+}
+
+/// Keeps every event of the game, in order, for tests to check.
+pub struct RecordingObserver<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    pub events: Vec<GameEvent<PositionType, MoveType>>
+}
+
+impl<PositionType, MoveType> RecordingObserver<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    pub fn new() -> RecordingObserver<PositionType, MoveType> {
+        RecordingObserver { events: vec![] }
+    }
+}
+
+impl<PositionType, MoveType> Default for RecordingObserver<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    fn default() -> Self {
+        RecordingObserver::new()
+    }
+}
+
+impl<PositionType, MoveType> GameObserver<PositionType, MoveType> for RecordingObserver<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    fn game_started(&mut self, position: &PositionType) {
+        self.events.push(GameEvent::GameStarted(*position));
+    }
+
+    fn move_played(&mut self, recorded_move: &RecordedMove<MoveType>) {
+        self.events.push(GameEvent::MovePlayed(*recorded_move));
+    }
+
+    fn position_changed(&mut self, position: &PositionType) {
+        self.events.push(GameEvent::PositionChanged(*position));
+    }
+
+    fn game_over(&mut self, record: &GameRecord<PositionType, MoveType>) {
+        self.events.push(GameEvent::GameOver(record.outcome));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::Write;
+    use std::rc::Rc;
+    use crate::core::engine::GameEngine;
+    use crate::core::observer::FileLogger;
+    use crate::core::test_support::negamax_player;
+    use crate::tictactoe::TicTacToe;
+
+    #[test]
+    pub fn test_file_logger_writes_every_event() {
+        let logger = Rc::new(RefCell::new(FileLogger::new(vec![])));
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)])
+            .with_observer(Box::new(logger.clone()));
+        engine.run().unwrap();
+
+        assert!(logger.borrow().error().is_none());
+        drop(engine);
+        let log = String::from_utf8(Rc::try_unwrap(logger).ok().unwrap().into_inner().into_inner()).unwrap();
+        let lines: Vec<&str> = log.lines().collect();
+        assert_eq!(lines.len(), 20);
+        assert!(lines[0].starts_with("game started"));
+        assert!(lines[1].starts_with("player 0 played"));
+        assert_eq!(lines[19], "game over after 9 moves: Some(Draw)");
+    }
+
+    /// A writer that counts how often it's flushed.
+    #[derive(Default)]
+    struct FlushCountingWriter {
+        flushes: usize
+    }
+
+    impl Write for FlushCountingWriter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    pub fn test_file_logger_flushes_once_the_game_is_over() {
+        let logger = Rc::new(RefCell::new(FileLogger::new(FlushCountingWriter::default())));
+        let mut engine = GameEngine::new(&TicTacToe {}, vec![negamax_player(&TicTacToe {}, 0), negamax_player(&TicTacToe {}, 1)])
+            .with_observer(Box::new(logger.clone()));
+        engine.step().unwrap();
+        assert_eq!(logger.borrow().writer.flushes, 0);
+
+        engine.run().unwrap();
+        assert_eq!(logger.borrow().writer.flushes, 1);
+    }
+}
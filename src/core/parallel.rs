@@ -0,0 +1,160 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use crate::core::game::AlternatingGame;
+use crate::core::move_strategy::{MoveStrategy, NegamaxMoveStrategy};
+use crate::core::position_evaluator::PositionEvaluation;
+
+/// A ParallelMoveStrategy spreads a {@link NegamaxMoveStrategy} across several threads by splitting the moves at the
+/// root between them. Each thread takes the next unsearched root move, builds a negamax strategy from the factory to
+/// search it, and repeats until every root move is searched. Move orderers and transposition tables that the factory
+/// sets up are used, but every root move gets fresh ones, not shared with or left over from any other root move.
+///
+/// Every root move is searched with the full window and its own strategy, independently of the others, so the result
+/// doesn't depend on which thread searched which move, or on how many threads there are. The price is that the root
+/// moves can't prune each other, or reuse what the searches of the other root moves learned.
+pub struct ParallelMoveStrategy<GameType>
+    where GameType: AlternatingGame,
+          GameType::Position: Sync,
+          GameType::Move: Send + Sync
+{
+    negamax_factory: Box<dyn Fn() -> NegamaxMoveStrategy<GameType> + Send + Sync>,
+    threads: usize
+}
+
+impl<GameType> ParallelMoveStrategy<GameType>
+    where GameType: AlternatingGame,
+          GameType::Position: Sync,
+          GameType::Move: Send + Sync
+{
+    /// Create a strategy that runs one thread per available CPU core, each searching with strategies from
+    /// `negamax_factory`. The factory is called afresh for every root move.
+    pub fn new(negamax_factory: Box<dyn Fn() -> NegamaxMoveStrategy<GameType> + Send + Sync>) -> ParallelMoveStrategy<GameType> {
+        ParallelMoveStrategy {
+            negamax_factory,
+            threads: thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1)
+        }
+    }
+
+    /// Create a strategy for the given game that runs one thread per available CPU core. Evaluations are for the side
+    /// to move, so the strategy can play for either player.
+    pub fn for_game(game: &GameType) -> ParallelMoveStrategy<GameType>
+        where GameType: Copy + Send + Sync + 'static {
+        let game = *game;
+        ParallelMoveStrategy::new(Box::new(move || NegamaxMoveStrategy::for_game(&game)))
+    }
+
+    /// Run the search on the given number of threads (at least one).
+    pub fn with_threads(mut self, threads: usize) -> ParallelMoveStrategy<GameType> {
+        self.threads = threads.max(1);
+        self
+    }
+}
+
+impl<GameType> MoveStrategy<GameType::Position, GameType::Move>
+for ParallelMoveStrategy<GameType>
+    where GameType: AlternatingGame,
+          GameType::Position: Sync,
+          GameType::Move: Send + Sync {
+    fn choose_move(&self, game: &GameType::Position) -> Option<GameType::Move> {
+        self.choose_evaluated_move(game).map(|(best_move, _)| best_move)
+    }
+
+    fn choose_evaluated_move(&self, game: &GameType::Position) -> Option<(GameType::Move, Option<PositionEvaluation>)> {
+        let moves = (self.negamax_factory)().root_moves(game);
+
+        let next_move = AtomicUsize::new(0);
+        let evaluations = Mutex::new(vec![PositionEvaluation::Losing(0); moves.len()]);
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(moves.len()) {
+                scope.spawn(|| loop {
+                    let index = next_move.fetch_add(1, Ordering::Relaxed);
+                    if index >= moves.len() {
+                        break;
+                    }
+                    // a strategy of its own for each root move, so that no search sees what another one cached
+                    let evaluation = (self.negamax_factory)().evaluate_root_move(game, &moves[index]);
+                    evaluations.lock().unwrap()[index] = evaluation;
+                });
+            }
+        });
+
+        // pick the first of the best moves in the search's order, as the sequential strategies do
+        let evaluations = evaluations.into_inner().unwrap();
+        let mut best_move = *moves.first()?;
+        let mut best_position_evaluation = PositionEvaluation::Losing(0);
+        for (r#move, evaluation) in moves.into_iter().zip(evaluations) {
+            if evaluation.improves_on(&best_position_evaluation) {
+                best_position_evaluation = evaluation;
+                best_move = r#move;
+            }
+        }
+        Some((best_move, Some(best_position_evaluation)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::connect_four::{ConnectFour, ConnectFourMoveGenerator, ConnectFourMoveOrderer, ConnectFourPosition};
+    use crate::core::game::Position;
+    use crate::core::move_strategy::{MoveStrategy, NegamaxMoveStrategy};
+    use crate::core::parallel::ParallelMoveStrategy;
+    use crate::core::r#move::MoveGenerator;
+
+    #[test]
+    pub fn test_parallel_matches_negamax() {
+        let negamax = NegamaxMoveStrategy::for_game(&ConnectFour {})
+            .with_max_depth(4);
+        let parallel = ParallelMoveStrategy::new(Box::new(|| NegamaxMoveStrategy::for_game(&ConnectFour {}).with_max_depth(4)))
+            .with_threads(4);
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        for red_move in move_generator.get_moves(&start) {
+            let game = start.apply(&red_move);
+            let expected = negamax.choose_move(&game).unwrap().column;
+            // whichever thread finishes first, the answer is the same
+            for _ in 0..3 {
+                assert_eq!(parallel.choose_move(&game).unwrap().column, expected);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_parallel_uses_the_negamax_move_orderer_and_transposition_table() {
+        let negamax = || NegamaxMoveStrategy::for_game(&ConnectFour {})
+            .with_max_depth(5)
+            .with_transposition_table(1 << 16)
+            .with_move_orderer(Box::new(ConnectFourMoveOrderer {}));
+        let parallel = ParallelMoveStrategy::new(Box::new(negamax))
+            .with_threads(4);
+
+        // every thread searches with its own copy of the factory's strategy, so they agree on the move
+        let game = ConnectFourPosition::new();
+        assert_eq!(parallel.choose_move(&game).unwrap().column, negamax().choose_move(&game).unwrap().column);
+    }
+
+    #[test]
+    pub fn test_threads_dont_change_results_with_a_transposition_table() {
+        let negamax = || NegamaxMoveStrategy::for_game(&ConnectFour {})
+            .with_max_depth(5)
+            .with_transposition_table(1 << 12)
+            .with_move_orderer(Box::new(ConnectFourMoveOrderer {}));
+        let single_threaded = ParallelMoveStrategy::new(Box::new(negamax))
+            .with_threads(1);
+        let multi_threaded = ParallelMoveStrategy::new(Box::new(negamax))
+            .with_threads(4);
+        let move_generator = ConnectFourMoveGenerator {};
+
+        let start = ConnectFourPosition::new();
+        for red_move in move_generator.get_moves(&start) {
+            let game = start.apply(&red_move);
+            let expected = single_threaded.choose_evaluated_move(&game).map(|(best_move, evaluation)| (best_move.column, evaluation));
+            for _ in 0..3 {
+                let actual = multi_threaded.choose_evaluated_move(&game).map(|(best_move, evaluation)| (best_move.column, evaluation));
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+}
@@ -1,9 +1,18 @@
 use crate::core::game::Position;
-use crate::core::move_strategy::MoveStrategy;
+use crate::core::move_strategy::{MoveStrategy, ObservationMoveStrategy};
+use crate::core::observation::ObservationModel;
+use crate::core::position_evaluator::PositionEvaluation;
 use crate::core::r#move::Move;
 
 pub trait Player<PositionType: Position<PositionType, MoveType>, MoveType: Move> {
-    fn pick_move(&self, game: &PositionType) -> MoveType;
+    /// Pick a move for the position, or None if the player has no moves, not even a pass.
+    fn pick_move(&self, game: &PositionType) -> Option<MoveType>;
+
+    /// Pick a move like {@link pick_move}, along with the player's own evaluation of it, or None for the evaluation
+    /// if the player doesn't evaluate its moves.
+    fn pick_evaluated_move(&self, game: &PositionType) -> Option<(MoveType, Option<PositionEvaluation>)> {
+        self.pick_move(game).map(|r#move| (r#move, None))
+    }
 }
 
 pub struct DefaultPlayer<PositionType, MoveType>
@@ -27,8 +36,76 @@ impl<PositionType, MoveType> Player<PositionType, MoveType> for DefaultPlayer<Po
     where PositionType: Position<PositionType, MoveType>,
           MoveType: Move
 {
-    fn pick_move(&self, game: &PositionType) -> MoveType {
+    fn pick_move(&self, game: &PositionType) -> Option<MoveType> {
         self.move_strategy.choose_move(game)
     }
+
+    fn pick_evaluated_move(&self, game: &PositionType) -> Option<(MoveType, Option<PositionEvaluation>)> {
+        self.move_strategy.choose_evaluated_move(game)
+    }
+}
+
+/// An ObservingPlayer plays a game with hidden information, so it picks its moves from what it can see of the
+/// position rather than from the position itself.
+pub trait ObservingPlayer<ObservationType, MoveType: Move> {
+    /// Pick a move for the observed position, or None if the player has no moves, not even a pass.
+    fn pick_move(&self, observation: &ObservationType) -> Option<MoveType>;
+}
+
+pub struct DefaultObservingPlayer<ObservationType, MoveType>
+    where MoveType: Move {
+    move_strategy: Box<dyn ObservationMoveStrategy<ObservationType, MoveType>>,
+}
+
+impl<ObservationType, MoveType> DefaultObservingPlayer<ObservationType, MoveType>
+    where MoveType: Move
+{
+    pub fn new(move_strategy: Box<dyn ObservationMoveStrategy<ObservationType, MoveType>>) -> DefaultObservingPlayer<ObservationType, MoveType> {
+        DefaultObservingPlayer::<ObservationType, MoveType> {
+            move_strategy
+        }
+    }
 }
 
+impl<ObservationType, MoveType> ObservingPlayer<ObservationType, MoveType> for DefaultObservingPlayer<ObservationType, MoveType>
+    where MoveType: Move
+{
+    fn pick_move(&self, observation: &ObservationType) -> Option<MoveType> {
+        self.move_strategy.choose_move(observation)
+    }
+}
+
+/// A Player that only shows an {@link ObservingPlayer} what the observation model lets it see of each position.
+pub struct ObservedPlayer<PositionType, MoveType, ObservationModelType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          ObservationModelType: ObservationModel<PositionType, MoveType> {
+    player_index: usize,
+    observation_model: ObservationModelType,
+    player: Box<dyn ObservingPlayer<ObservationModelType::Observation, MoveType>>
+}
+
+impl<PositionType, MoveType, ObservationModelType> ObservedPlayer<PositionType, MoveType, ObservationModelType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          ObservationModelType: ObservationModel<PositionType, MoveType>
+{
+    /// Show the player with the given index its observations of each position.
+    pub fn new(player_index: usize, observation_model: ObservationModelType, player: Box<dyn ObservingPlayer<ObservationModelType::Observation, MoveType>>) -> ObservedPlayer<PositionType, MoveType, ObservationModelType> {
+        ObservedPlayer {
+            player_index,
+            observation_model,
+            player
+        }
+    }
+}
+
+impl<PositionType, MoveType, ObservationModelType> Player<PositionType, MoveType> for ObservedPlayer<PositionType, MoveType, ObservationModelType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move,
+          ObservationModelType: ObservationModel<PositionType, MoveType>
+{
+    fn pick_move(&self, game: &PositionType) -> Option<MoveType> {
+        self.player.pick_move(&self.observation_model.observe(game, self.player_index))
+    }
+}
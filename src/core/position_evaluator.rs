@@ -1,45 +1,135 @@
 use std::cmp::Ordering;
-use crate::core::game::Position;
+use std::fmt::{Display, Formatter};
+use std::ops::Neg;
+use crate::core::game::{Game, Position};
 use crate::core::r#move::Move;
 
 /// An evaluation of a game position. For sophisticated position evaluators and strategies, this should incorporate
 /// any information discovered about positions reachable from the current position, especially positions that can be
 /// forced by any player.
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug)]
 pub enum PositionEvaluation {
     /// Winning indicates that the game position leads to a forced win for the current player if the player
-    /// plays perfectly.
-    Winning,
+    /// plays perfectly, and contains the number of plies until the game is won. A quicker win is a better one.
+    Winning(usize),
     /// Losing indicates that the game position leads to a forced loss for the current player if the opposing player(s)
-    /// play(s) perfectly.
-    Losing,
+    /// play(s) perfectly, and contains the number of plies until the game is lost. A slower loss is a better one,
+    /// since it leaves the opponent more chances to go wrong.
+    Losing(usize),
+    /// Draw indicates that the game position leads to a forced draw if both players play perfectly. A draw is worth
+    /// the same as an even estimate, so Draw compares equal to Estimate(0.0); match on the variant to tell a proven
+    /// draw from a position that only looks balanced.
+    Draw,
     /// Estimate means that it's not clear whether the position is winning or losing for the current player, and
     /// contains a numerical estimate of the advantage to the player from the position. Positive means the
     /// player appears to be at an advantage, and negative means that the player appears to be at a disadvantage.
     Estimate(f64)
 }
 
+impl PositionEvaluation {
+    /// The smallest evaluation that's better than this one, or None if nothing is better.
+    ///
+    /// A search whose window runs from an evaluation to its successor is a null-window search. It can't determine a
+    /// position's exact evaluation, but it answers whether the position is better than the evaluation, which is
+    /// often enough to rule a move out cheaply.
+    pub fn successor(&self) -> Option<PositionEvaluation> {
+        match *self {
+            PositionEvaluation::Winning(0) => None,
+            PositionEvaluation::Winning(plies) => Some(PositionEvaluation::Winning(plies - 1)),
+            PositionEvaluation::Losing(usize::MAX) => Some(PositionEvaluation::Estimate(f64::NEG_INFINITY)),
+            PositionEvaluation::Losing(plies) => Some(PositionEvaluation::Losing(plies + 1)),
+            PositionEvaluation::Draw => PositionEvaluation::Estimate(0.0).successor(),
+            PositionEvaluation::Estimate(x) if x == f64::INFINITY => Some(PositionEvaluation::Winning(usize::MAX)),
+            PositionEvaluation::Estimate(x) => Some(PositionEvaluation::Estimate(x.next_up()))
+        }
+    }
+
+    /// The evaluation of a position whose best continuation leads to a position with this evaluation: a win or loss
+    /// is one ply further away.
+    ///
+    /// Searches apply this to every evaluation they pass back up the tree, which is what makes them prefer the
+    /// quickest win and the slowest loss.
+    pub fn add_ply(&self) -> PositionEvaluation {
+        match *self {
+            PositionEvaluation::Winning(plies) => PositionEvaluation::Winning(plies.saturating_add(1)),
+            PositionEvaluation::Losing(plies) => PositionEvaluation::Losing(plies.saturating_add(1)),
+            other => other
+        }
+    }
+
+    /// Whether a search should prefer this evaluation to `best`, the best it has found so far. That's so if this
+    /// evaluation is better, but also if it's only as good and `best` is a {@link #Draw}: a draw that ties with an
+    /// estimate isn't proven, because the estimated line may turn out better. Searches that minimize can compare the
+    /// negations.
+    pub fn improves_on(&self, best: &PositionEvaluation) -> bool {
+        match (*self, *best) {
+            (PositionEvaluation::Draw, _) => *self > *best,
+            (_, PositionEvaluation::Draw) => *self >= *best,
+            _ => *self > *best
+        }
+    }
+
+    /// The inverse of {@link #add_ply}, for passing alpha-beta bounds down the tree. A win or loss that's already
+    /// at hand stays at hand.
+    pub fn remove_ply(&self) -> PositionEvaluation {
+        match *self {
+            PositionEvaluation::Winning(plies) => PositionEvaluation::Winning(plies.saturating_sub(1)),
+            PositionEvaluation::Losing(plies) => PositionEvaluation::Losing(plies.saturating_sub(1)),
+            other => other
+        }
+    }
+}
+
 impl PartialOrd for PositionEvaluation {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (PositionEvaluation::Winning, PositionEvaluation::Winning) => Some(Ordering::Equal),
-            (PositionEvaluation::Winning, _) => Some(Ordering::Greater),
-            (_, PositionEvaluation::Winning) => Some(Ordering::Less),
-            (PositionEvaluation::Losing, PositionEvaluation::Losing) => Some(Ordering::Equal),
-            (PositionEvaluation::Losing, _) => Some(Ordering::Less),
-            (_, PositionEvaluation::Losing) => Some(Ordering::Greater),
-            (PositionEvaluation::Estimate(x), PositionEvaluation::Estimate(y)) => x.partial_cmp(y)
+        // a draw is ordered like the even estimate it's worth
+        let value = |position_evaluation: &PositionEvaluation| match position_evaluation {
+            PositionEvaluation::Draw => PositionEvaluation::Estimate(0.0),
+            _ => *position_evaluation
+        };
+        match (value(self), value(other)) {
+            (PositionEvaluation::Winning(x), PositionEvaluation::Winning(y)) => Some(y.cmp(&x)),
+            (PositionEvaluation::Winning(_), _) => Some(Ordering::Greater),
+            (_, PositionEvaluation::Winning(_)) => Some(Ordering::Less),
+            (PositionEvaluation::Losing(x), PositionEvaluation::Losing(y)) => Some(x.cmp(&y)),
+            (PositionEvaluation::Losing(_), _) => Some(Ordering::Less),
+            (_, PositionEvaluation::Losing(_)) => Some(Ordering::Greater),
+            (PositionEvaluation::Estimate(x), PositionEvaluation::Estimate(y)) => x.partial_cmp(&y),
+            (PositionEvaluation::Draw, _) | (_, PositionEvaluation::Draw) => unreachable!("Draws are compared as estimates")
         }
     }
 }
 
 impl PartialEq<Self> for PositionEvaluation {
     fn eq(&self, other: &Self) -> bool {
-        match (self, other) {
-            (PositionEvaluation::Winning, PositionEvaluation::Winning) => true,
-            (PositionEvaluation::Losing, PositionEvaluation::Losing) => true,
-            (PositionEvaluation::Estimate(x), PositionEvaluation::Estimate(y)) => x== y,
-            _ => false
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl Neg for PositionEvaluation {
+    type Output = PositionEvaluation;
+
+    /// The same evaluation from the opponent's point of view.
+    fn neg(self) -> PositionEvaluation {
+        match self {
+            PositionEvaluation::Winning(plies) => PositionEvaluation::Losing(plies),
+            PositionEvaluation::Losing(plies) => PositionEvaluation::Winning(plies),
+            PositionEvaluation::Draw => PositionEvaluation::Draw,
+            PositionEvaluation::Estimate(x) => PositionEvaluation::Estimate(-x)
+        }
+    }
+}
+
+/// Displays wins and losses by how many moves the winner needs, counting only the winner's own moves, as in "mate in 2".
+impl Display for PositionEvaluation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PositionEvaluation::Winning(0) => write!(f, "won"),
+            PositionEvaluation::Winning(plies) => write!(f, "mate in {}", plies.div_ceil(2)),
+            PositionEvaluation::Losing(0) => write!(f, "lost"),
+            PositionEvaluation::Losing(plies) => write!(f, "mated in {}", plies.div_ceil(2)),
+            PositionEvaluation::Draw => write!(f, "draw"),
+            PositionEvaluation::Estimate(x) => write!(f, "{:+.2}", x)
         }
     }
 }
@@ -53,20 +143,89 @@ pub trait PositionEvaluator<PositionType, MoveType>
     fn evaluate(&self, position: &PositionType) -> PositionEvaluation;
 }
 
+/// A SideToMovePositionEvaluator evaluates a position for whichever player is about to move in it, rather than for a
+/// fixed player. That means a single evaluator can serve every player in a game, and that the evaluation of a position
+/// for the player who just moved is the negation of its evaluation for the player to move.
+pub trait SideToMovePositionEvaluator<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+
+    /// Evaluate a position for the player to move. Like {@link PositionEvaluator#evaluate}, this should incorporate
+    /// only the position itself.
+    fn evaluate_for_side_to_move(&self, position: &PositionType) -> PositionEvaluation;
+}
+
+/// A MultiPlayerPositionEvaluator evaluates a position for every player at once, which is what searches need in games
+/// with more than two players: there, one player's gain isn't simply every other player's loss, so no single
+/// evaluation can stand for the position.
+pub trait MultiPlayerPositionEvaluator<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move {
+
+    /// Evaluate a position for each player, indexed by player. Like {@link PositionEvaluator#evaluate}, this should
+    /// incorporate only the position itself.
+    fn evaluate_for_each_player(&self, position: &PositionType) -> Vec<PositionEvaluation>;
+}
+
+/// Evaluates a position for every player of a game, by asking the game's position evaluator for each player in turn.
+/// That gives any game a {@link MultiPlayerPositionEvaluator}, without one of its own.
+pub struct EachPlayerPositionEvaluator<GameType>
+    where GameType: Game
+{
+    position_evaluators: Vec<GameType::PositionEvaluator>
+}
+
+impl<GameType> EachPlayerPositionEvaluator<GameType>
+    where GameType: Game
+{
+    pub fn for_game(game: &GameType) -> EachPlayerPositionEvaluator<GameType> {
+        EachPlayerPositionEvaluator {
+            position_evaluators: (0..game.player_count()).map(|player| game.position_evaluator(player)).collect()
+        }
+    }
+}
+
+impl<GameType> MultiPlayerPositionEvaluator<GameType::Position, GameType::Move> for EachPlayerPositionEvaluator<GameType>
+    where GameType: Game {
+    fn evaluate_for_each_player(&self, position: &GameType::Position) -> Vec<PositionEvaluation> {
+        self.position_evaluators.iter().map(|position_evaluator| position_evaluator.evaluate(position)).collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cmp::Ordering;
-    use crate::core::position_evaluator::PositionEvaluation;
+    use crate::core::position_evaluator::{EachPlayerPositionEvaluator, MultiPlayerPositionEvaluator, PositionEvaluation};
+    use crate::core::test_support::{Countdown, CountdownPosition};
+
+    #[test]
+    pub fn test_each_player_position_evaluator() {
+        let position_evaluator = EachPlayerPositionEvaluator::for_game(&Countdown::default());
+        let position_evaluations = position_evaluator.evaluate_for_each_player(&CountdownPosition { counters: 0, next_player: 1 });
+        // player 0 took the last counter
+        assert!(matches!(position_evaluations[..], [PositionEvaluation::Winning(0), PositionEvaluation::Losing(0), PositionEvaluation::Losing(0)]));
+    }
+
+    #[test]
+    pub fn test_improves_on() {
+        assert!(PositionEvaluation::Estimate(1.0).improves_on(&PositionEvaluation::Estimate(0.0)));
+        assert!(!PositionEvaluation::Estimate(0.0).improves_on(&PositionEvaluation::Estimate(0.0)));
+        // an even estimate is as good as a draw, but it isn't proven
+        assert!(PositionEvaluation::Estimate(0.0).improves_on(&PositionEvaluation::Draw));
+        assert!(!PositionEvaluation::Draw.improves_on(&PositionEvaluation::Estimate(0.0)));
+        assert!(!PositionEvaluation::Draw.improves_on(&PositionEvaluation::Draw));
+        assert!(!PositionEvaluation::Estimate(-1.0).improves_on(&PositionEvaluation::Draw));
+    }
 
     #[test]
     pub fn test_partial_ord_for_position_evaluation() {
-        let ordering = PositionEvaluation::Winning.partial_cmp(&PositionEvaluation::Winning);
+        let ordering = PositionEvaluation::Winning(0).partial_cmp(&PositionEvaluation::Winning(0));
         assert_eq!(ordering.unwrap(), Ordering::Equal);
-        let ordering = PositionEvaluation::Winning.partial_cmp(&PositionEvaluation::Estimate(5.0));
+        let ordering = PositionEvaluation::Winning(0).partial_cmp(&PositionEvaluation::Estimate(5.0));
         assert_eq!(ordering.unwrap(), Ordering::Greater);
-        let ordering = PositionEvaluation::Winning.partial_cmp(&PositionEvaluation::Losing);
+        let ordering = PositionEvaluation::Winning(0).partial_cmp(&PositionEvaluation::Losing(0));
         assert_eq!(ordering.unwrap(), Ordering::Greater);
-        let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Winning);
+        let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Winning(0));
         assert_eq!(ordering.unwrap(), Ordering::Less);
         let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Estimate(-5.0));
         assert_eq!(ordering.unwrap(), Ordering::Greater);
@@ -74,13 +233,73 @@ mod tests {
         assert_eq!(ordering.unwrap(), Ordering::Equal);
         let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Estimate(10.0));
         assert_eq!(ordering.unwrap(), Ordering::Less);
-        let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Losing);
+        let ordering = PositionEvaluation::Estimate(5.0).partial_cmp(&PositionEvaluation::Losing(0));
         assert_eq!(ordering.unwrap(), Ordering::Greater);
-        let ordering = PositionEvaluation::Losing.partial_cmp(&PositionEvaluation::Winning);
+        let ordering = PositionEvaluation::Losing(0).partial_cmp(&PositionEvaluation::Winning(0));
+        assert_eq!(ordering.unwrap(), Ordering::Less);
+        let ordering = PositionEvaluation::Losing(0).partial_cmp(&PositionEvaluation::Estimate(0.0));
         assert_eq!(ordering.unwrap(), Ordering::Less);
-        let ordering = PositionEvaluation::Losing.partial_cmp(&PositionEvaluation::Estimate(0.0));
+        let ordering = PositionEvaluation::Losing(0).partial_cmp(&PositionEvaluation::Losing(0));
+        assert_eq!(ordering.unwrap(), Ordering::Equal);
+        let ordering = PositionEvaluation::Draw.partial_cmp(&PositionEvaluation::Draw);
+        assert_eq!(ordering.unwrap(), Ordering::Equal);
+        let ordering = PositionEvaluation::Draw.partial_cmp(&PositionEvaluation::Winning(0));
         assert_eq!(ordering.unwrap(), Ordering::Less);
-        let ordering = PositionEvaluation::Losing.partial_cmp(&PositionEvaluation::Losing);
+        let ordering = PositionEvaluation::Draw.partial_cmp(&PositionEvaluation::Losing(0));
+        assert_eq!(ordering.unwrap(), Ordering::Greater);
+        let ordering = PositionEvaluation::Draw.partial_cmp(&PositionEvaluation::Estimate(0.0));
         assert_eq!(ordering.unwrap(), Ordering::Equal);
+        let ordering = PositionEvaluation::Draw.partial_cmp(&PositionEvaluation::Estimate(0.5));
+        assert_eq!(ordering.unwrap(), Ordering::Less);
+        let ordering = PositionEvaluation::Estimate(-0.5).partial_cmp(&PositionEvaluation::Draw);
+        assert_eq!(ordering.unwrap(), Ordering::Less);
+        let ordering = PositionEvaluation::Winning(1).partial_cmp(&PositionEvaluation::Winning(3));
+        assert_eq!(ordering.unwrap(), Ordering::Greater);
+        let ordering = PositionEvaluation::Losing(1).partial_cmp(&PositionEvaluation::Losing(3));
+        assert_eq!(ordering.unwrap(), Ordering::Less);
+        let ordering = PositionEvaluation::Losing(100).partial_cmp(&PositionEvaluation::Estimate(-1000.0));
+        assert_eq!(ordering.unwrap(), Ordering::Less);
+    }
+
+    #[test]
+    pub fn test_add_and_remove_ply() {
+        assert_eq!(PositionEvaluation::Winning(0).add_ply(), PositionEvaluation::Winning(1));
+        assert_eq!(PositionEvaluation::Losing(2).add_ply(), PositionEvaluation::Losing(3));
+        assert_eq!(PositionEvaluation::Estimate(5.0).add_ply(), PositionEvaluation::Estimate(5.0));
+        assert_eq!(PositionEvaluation::Winning(3).remove_ply(), PositionEvaluation::Winning(2));
+        assert_eq!(PositionEvaluation::Losing(0).remove_ply(), PositionEvaluation::Losing(0));
+        assert!(matches!(PositionEvaluation::Draw.add_ply(), PositionEvaluation::Draw));
+    }
+
+    #[test]
+    pub fn test_display() {
+        assert_eq!(PositionEvaluation::Winning(0).to_string(), "won");
+        assert_eq!(PositionEvaluation::Winning(1).to_string(), "mate in 1");
+        assert_eq!(PositionEvaluation::Winning(3).to_string(), "mate in 2");
+        assert_eq!(PositionEvaluation::Losing(2).to_string(), "mated in 1");
+        assert_eq!(PositionEvaluation::Draw.to_string(), "draw");
+        assert_eq!(PositionEvaluation::Estimate(1.5).to_string(), "+1.50");
+    }
+
+    #[test]
+    pub fn test_successor() {
+        assert_eq!(PositionEvaluation::Winning(0).successor(), None);
+        assert_eq!(PositionEvaluation::Winning(3).successor(), Some(PositionEvaluation::Winning(2)));
+        assert_eq!(PositionEvaluation::Losing(0).successor(), Some(PositionEvaluation::Losing(1)));
+        assert_eq!(PositionEvaluation::Losing(usize::MAX).successor(), Some(PositionEvaluation::Estimate(f64::NEG_INFINITY)));
+        assert_eq!(PositionEvaluation::Estimate(f64::INFINITY).successor(), Some(PositionEvaluation::Winning(usize::MAX)));
+        assert!(PositionEvaluation::Draw.successor().unwrap() > PositionEvaluation::Draw);
+
+        let successor = PositionEvaluation::Estimate(1.0).successor().unwrap();
+        assert!(successor > PositionEvaluation::Estimate(1.0));
+        assert!(successor < PositionEvaluation::Estimate(1.0 + 1e-9));
+    }
+
+    #[test]
+    pub fn test_neg_for_position_evaluation() {
+        assert_eq!(-PositionEvaluation::Winning(3), PositionEvaluation::Losing(3));
+        assert_eq!(-PositionEvaluation::Losing(0), PositionEvaluation::Winning(0));
+        assert!(matches!(-PositionEvaluation::Draw, PositionEvaluation::Draw));
+        assert_eq!(-PositionEvaluation::Estimate(5.0), PositionEvaluation::Estimate(-5.0));
     }
 }
\ No newline at end of file
@@ -0,0 +1,60 @@
+/// A small, seedable pseudo-random number generator for strategies and engines that need reproducible randomness.
+///
+/// It's not suitable for anything security-sensitive, but it's fast and has no dependencies, and the same seed always
+/// produces the same sequence of numbers.
+#[derive(Clone, Debug)]
+pub struct Random {
+    state: u64
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let (state, output) = split_mix_64(self.state);
+        self.state = state;
+        output
+    }
+
+    /// A number uniformly distributed in [0, 1).
+    pub fn next_f64(&mut self) -> f64 {
+        // the top 53 bits fill an f64's mantissa exactly
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// A number uniformly distributed in [0, bound). Panics if the bound is 0.
+    pub fn below(&mut self, bound: usize) -> usize {
+        assert!(bound > 0, "Can't pick a number below 0");
+        // multiply-shift instead of modulo, to avoid favoring small numbers
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+
+    /// One of the given items, each picked with the probability paired with it. The probabilities should add up to
+    /// 1; if rounding leaves them a little short, the last item makes up the difference. Panics if there are no
+    /// items.
+    pub fn pick_weighted<T>(&mut self, items: &[(T, f64)]) -> T
+        where T: Copy {
+        assert!(!items.is_empty(), "Can't pick from no items");
+        let mut remaining = self.next_f64();
+        for &(item, probability) in items {
+            if remaining < probability {
+                return item;
+            }
+            remaining -= probability;
+        }
+        items[items.len() - 1].0
+    }
+}
+
+/// One step of the SplitMix64 generator, returning the next state and its output. It's small enough to run in a
+/// const fn, and although it's no use for cryptography, its outputs are statistically sound enough to drive playouts
+/// and dice.
+pub const fn split_mix_64(state: u64) -> (u64, u64) {
+    let state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    (state, z ^ (z >> 31))
+}
@@ -0,0 +1,165 @@
+use std::time::Duration;
+use crate::core::game::{Game, GameOutcome, Position};
+use crate::core::position_evaluator::PositionEvaluation;
+use crate::core::r#move::Move;
+
+/// A {@link GameRecord} built from a {@link Game}'s types.
+pub type GameRecordFor<GameType> = GameRecord<<GameType as Game>::Position, <GameType as Game>::Move>;
+
+/// A move played in a recorded game, along with what the engine learned while it was played.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RecordedMove<MoveType>
+    where MoveType: Move
+{
+    pub r#move: MoveType,
+    /// The index of the player who played the move, or None if it was a chance event.
+    pub player: Option<usize>,
+    /// How long the player took to pick the move. Chance events take no time.
+    pub time_taken: Duration,
+    /// The evaluation the player's own search gave the move, from that player's point of view, or None if it was a
+    /// chance event, a move submitted to the engine, or a player that doesn't evaluate its moves.
+    pub evaluation: Option<PositionEvaluation>
+}
+
+/// A GameRecord is everything needed to play a game back: the position it started from, every move played, in
+/// order, and how it ended. The {@link GameEngine} returns one when it finishes a game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameRecord<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    pub initial_position: PositionType,
+    pub moves: Vec<RecordedMove<MoveType>>,
+    /// How the game ended, or None if the oracle couldn't say whose turn it was before the game ended.
+    pub outcome: Option<GameOutcome>
+}
+
+impl<PositionType, MoveType> GameRecord<PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    /// The position at the end of the game.
+    pub fn final_position(&self) -> PositionType {
+        self.moves.iter().fold(self.initial_position, |position, recorded_move| position.apply(&recorded_move.r#move))
+    }
+
+    /// Start replaying the game from its initial position.
+    pub fn replay(&self) -> GameReplay<'_, PositionType, MoveType> {
+        GameReplay::new(self)
+    }
+}
+
+/// A GameReplay steps forward and back through a {@link GameRecord}, one move at a time, to review a finished game.
+pub struct GameReplay<'a, PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    record: &'a GameRecord<PositionType, MoveType>,
+    /// The positions reached so far, starting with the initial position, so that stepping back is free.
+    positions: Vec<PositionType>
+}
+
+impl<'a, PositionType, MoveType> GameReplay<'a, PositionType, MoveType>
+    where PositionType: Position<PositionType, MoveType>,
+          MoveType: Move
+{
+    pub fn new(record: &'a GameRecord<PositionType, MoveType>) -> GameReplay<'a, PositionType, MoveType> {
+        GameReplay {
+            record,
+            positions: vec![record.initial_position]
+        }
+    }
+
+    /// The number of moves played to reach the current position.
+    pub fn ply(&self) -> usize {
+        self.positions.len() - 1
+    }
+
+    pub fn position(&self) -> PositionType {
+        self.positions[self.ply()]
+    }
+
+    /// The move that reached the current position, or None at the start of the game.
+    pub fn last_move(&self) -> Option<&'a RecordedMove<MoveType>> {
+        match self.ply() {
+            0 => None,
+            ply => self.record.moves.get(ply - 1)
+        }
+    }
+
+    /// The move to be played from the current position, or None at the end of the game.
+    pub fn next_move(&self) -> Option<&'a RecordedMove<MoveType>> {
+        self.record.moves.get(self.ply())
+    }
+
+    /// Play the next move, and return it, or return None without moving if the game is over.
+    pub fn step_forward(&mut self) -> Option<&'a RecordedMove<MoveType>> {
+        let recorded_move = self.next_move()?;
+        self.positions.push(self.position().apply(&recorded_move.r#move));
+        Some(recorded_move)
+    }
+
+    /// Take back the last move, and return it, or return None without moving if at the start of the game.
+    pub fn step_back(&mut self) -> Option<&'a RecordedMove<MoveType>> {
+        let recorded_move = self.last_move()?;
+        self.positions.pop();
+        Some(recorded_move)
+    }
+
+    /// Step forward or back to the position after the given number of moves, or to the end of the game if it had
+    /// fewer moves than that.
+    pub fn go_to(&mut self, ply: usize) {
+        self.positions.truncate(ply + 1);
+        while self.ply() < ply && self.step_forward().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+    use crate::core::game::GameOutcome;
+    use crate::core::record::{GameRecord, RecordedMove};
+    use crate::tictactoe::{Piece, TicTacToeMove, TicTacToePosition};
+
+    fn record() -> GameRecord<TicTacToePosition, TicTacToeMove> {
+        let moves = [(0, Piece::X), (4, Piece::O), (8, Piece::X)].iter()
+            .enumerate()
+            .map(|(ply, &(position, piece))| RecordedMove {
+                r#move: TicTacToeMove { position, piece },
+                player: Some(ply % 2),
+                time_taken: Duration::ZERO,
+                evaluation: None
+            })
+            .collect();
+        GameRecord { initial_position: TicTacToePosition::new(), moves, outcome: Some(GameOutcome::Draw) }
+    }
+
+    #[test]
+    pub fn test_replay_steps_both_ways() {
+        let record = record();
+        let mut replay = record.replay();
+        assert_eq!(replay.step_back(), None);
+        assert_eq!(replay.step_forward().map(|recorded_move| recorded_move.r#move.position), Some(0));
+        assert_eq!(replay.step_forward().map(|recorded_move| recorded_move.r#move.position), Some(4));
+        assert_eq!(replay.position().board[4], Some(Piece::O));
+
+        assert_eq!(replay.step_back().map(|recorded_move| recorded_move.r#move.position), Some(4));
+        assert_eq!(replay.ply(), 1);
+        assert_eq!(replay.position().board[4], None);
+        assert_eq!(replay.position().board[0], Some(Piece::X));
+    }
+
+    #[test]
+    pub fn test_replay_goes_to_a_ply() {
+        let record = record();
+        let mut replay = record.replay();
+        replay.go_to(10);
+        assert_eq!(replay.ply(), 3);
+        assert_eq!(replay.next_move(), None);
+        assert_eq!(replay.position().board, record.final_position().board);
+
+        replay.go_to(0);
+        assert_eq!(replay.position().board, TicTacToePosition::new().board);
+        assert_eq!(replay.last_move(), None);
+    }
+}
@@ -0,0 +1,248 @@
+use crate::core::game::{AlternatingGame, DeterministicGame, Game, GameOutcome, Oracle, Position};
+use crate::core::max_n::MaxNMoveStrategy;
+use crate::core::move_strategy::NegamaxMoveStrategy;
+use crate::core::player::{DefaultPlayer, Player};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator, SideToMovePositionEvaluator};
+use crate::core::r#move::{Move, MoveGenerator};
+
+/// A player that searches the whole game with a {@link NegamaxMoveStrategy}. It plays either side, and takes the
+/// player's index only so that it can be used as a player factory.
+pub(crate) fn negamax_player<GameType>(game: &GameType, _player: usize) -> Box<dyn Player<GameType::Position, GameType::Move>>
+    where GameType: AlternatingGame + 'static {
+    Box::new(DefaultPlayer::new(Box::new(NegamaxMoveStrategy::for_game(game))))
+}
+
+/// A player that searches the whole game with a {@link MaxNMoveStrategy}, for games with more than two players.
+pub(crate) fn max_n_player<GameType>(game: &GameType, _player: usize) -> Box<dyn Player<GameType::Position, GameType::Move>>
+    where GameType: DeterministicGame + 'static {
+    Box::new(DefaultPlayer::new(Box::new(MaxNMoveStrategy::for_game(game))))
+}
+
+/// A node of a {@link TreeGame}.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum TreeNode {
+    /// A position where play goes on, to the nodes with the given indices. It's estimated to be worth the given
+    /// amount to player 0.
+    Branch(&'static [usize], f64),
+    /// A position where the game is over.
+    Terminal(GameOutcome)
+}
+
+/// A two-player game played on a fixed tree of nodes, for testing searches on positions that would be hard to set
+/// up in a real game. Play starts at node 0 with player 0 to move, and the players take turns to pick a child of the
+/// current node.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TreeGame {
+    pub(crate) nodes: &'static [TreeNode]
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TreePosition {
+    nodes: &'static [TreeNode],
+    pub(crate) node: usize,
+    player_to_move: usize
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct TreeMove {
+    pub(crate) node: usize
+}
+
+impl Move for TreeMove {}
+
+impl Position<TreePosition, TreeMove> for TreePosition {
+    fn apply(&self, m: &TreeMove) -> TreePosition {
+        TreePosition { nodes: self.nodes, node: m.node, player_to_move: 1 - self.player_to_move }
+    }
+}
+
+pub(crate) struct TreeMoveGenerator {}
+
+impl MoveGenerator<TreePosition, TreeMove> for TreeMoveGenerator {
+    fn get_moves(&self, game: &TreePosition) -> Vec<TreeMove> {
+        match game.nodes[game.node] {
+            TreeNode::Branch(children, _) => children.iter().map(|&node| TreeMove { node }).collect(),
+            TreeNode::Terminal(_) => vec![]
+        }
+    }
+}
+
+pub(crate) struct TreeOracle {}
+
+impl Oracle<TreePosition, TreeMove> for TreeOracle {
+    fn next_player(&self, game: &TreePosition) -> Option<usize> {
+        match game.nodes[game.node] {
+            TreeNode::Branch(_, _) => Some(game.player_to_move),
+            TreeNode::Terminal(_) => None
+        }
+    }
+
+    fn outcome(&self, game: &TreePosition) -> Option<GameOutcome> {
+        match game.nodes[game.node] {
+            TreeNode::Branch(_, _) => None,
+            TreeNode::Terminal(outcome) => Some(outcome)
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TreePositionEvaluator {
+    player: usize
+}
+
+impl PositionEvaluator<TreePosition, TreeMove> for TreePositionEvaluator {
+    fn evaluate(&self, position: &TreePosition) -> PositionEvaluation {
+        match position.nodes[position.node] {
+            TreeNode::Branch(_, estimate) if self.player == 0 => PositionEvaluation::Estimate(estimate),
+            TreeNode::Branch(_, estimate) => PositionEvaluation::Estimate(-estimate),
+            TreeNode::Terminal(GameOutcome::Win(winner)) if winner == self.player => PositionEvaluation::Winning(0),
+            TreeNode::Terminal(GameOutcome::Win(_)) => PositionEvaluation::Losing(0),
+            TreeNode::Terminal(GameOutcome::Draw) => PositionEvaluation::Draw
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct TreeSideToMovePositionEvaluator {}
+
+impl SideToMovePositionEvaluator<TreePosition, TreeMove> for TreeSideToMovePositionEvaluator {
+    fn evaluate_for_side_to_move(&self, position: &TreePosition) -> PositionEvaluation {
+        TreePositionEvaluator { player: position.player_to_move }.evaluate(position)
+    }
+}
+
+impl Game for TreeGame {
+    type Position = TreePosition;
+    type Move = TreeMove;
+    type MoveGenerator = TreeMoveGenerator;
+    type Oracle = TreeOracle;
+    type PositionEvaluator = TreePositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> TreePosition {
+        TreePosition { nodes: self.nodes, node: 0, player_to_move: 0 }
+    }
+
+    fn move_generator(&self) -> TreeMoveGenerator {
+        TreeMoveGenerator {}
+    }
+
+    fn oracle(&self) -> TreeOracle {
+        TreeOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> TreePositionEvaluator {
+        TreePositionEvaluator { player }
+    }
+}
+
+impl DeterministicGame for TreeGame {}
+
+impl AlternatingGame for TreeGame {
+    type SideToMovePositionEvaluator = TreeSideToMovePositionEvaluator;
+
+    fn side_to_move_position_evaluator(&self) -> TreeSideToMovePositionEvaluator {
+        TreeSideToMovePositionEvaluator {}
+    }
+}
+
+/// A three-player counting game: players take turns to take one or two counters from a pile, and whoever takes
+/// the last counter wins.
+#[derive(Copy, Clone, Debug, Default)]
+pub(crate) struct Countdown {
+    pub(crate) counters: usize
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CountdownPosition {
+    pub(crate) counters: usize,
+    pub(crate) next_player: usize
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CountdownMove {
+    pub(crate) take: usize
+}
+
+impl Move for CountdownMove {}
+
+impl Position<CountdownPosition, CountdownMove> for CountdownPosition {
+    fn apply(&self, m: &CountdownMove) -> CountdownPosition {
+        CountdownPosition { counters: self.counters - m.take, next_player: (self.next_player + 1) % 3 }
+    }
+}
+
+pub(crate) struct CountdownMoveGenerator {}
+
+impl MoveGenerator<CountdownPosition, CountdownMove> for CountdownMoveGenerator {
+    fn get_moves(&self, game: &CountdownPosition) -> Vec<CountdownMove> {
+        (1..=game.counters.min(2)).map(|take| CountdownMove { take }).collect()
+    }
+}
+
+pub(crate) struct CountdownOracle {}
+
+impl Oracle<CountdownPosition, CountdownMove> for CountdownOracle {
+    fn next_player(&self, game: &CountdownPosition) -> Option<usize> {
+        match game.counters {
+            0 => None,
+            _ => Some(game.next_player)
+        }
+    }
+
+    fn outcome(&self, game: &CountdownPosition) -> Option<GameOutcome> {
+        match game.counters {
+            0 => Some(GameOutcome::Win((game.next_player + 2) % 3)),
+            _ => None
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct CountdownPositionEvaluator {
+    player: usize
+}
+
+impl PositionEvaluator<CountdownPosition, CountdownMove> for CountdownPositionEvaluator {
+    fn evaluate(&self, position: &CountdownPosition) -> PositionEvaluation {
+        let outcome = CountdownOracle {}.outcome(position);
+        match outcome {
+            Some(GameOutcome::Win(winner)) if winner == self.player => PositionEvaluation::Winning(0),
+            Some(_) => PositionEvaluation::Losing(0),
+            None => PositionEvaluation::Estimate(0.0)
+        }
+    }
+}
+
+impl Game for Countdown {
+    type Position = CountdownPosition;
+    type Move = CountdownMove;
+    type MoveGenerator = CountdownMoveGenerator;
+    type Oracle = CountdownOracle;
+    type PositionEvaluator = CountdownPositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        3
+    }
+
+    fn initial_position(&self) -> CountdownPosition {
+        CountdownPosition { counters: self.counters, next_player: 0 }
+    }
+
+    fn move_generator(&self) -> CountdownMoveGenerator {
+        CountdownMoveGenerator {}
+    }
+
+    fn oracle(&self) -> CountdownOracle {
+        CountdownOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> CountdownPositionEvaluator {
+        CountdownPositionEvaluator { player }
+    }
+}
+
+impl DeterministicGame for Countdown {}
@@ -0,0 +1,384 @@
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use crate::core::engine::GameEngineError;
+use crate::core::game::Game;
+use crate::core::player::Player;
+use crate::core::r#match::{Match, MatchResult, PlayerFactory};
+
+/// A player configuration entered in a {@link Tournament}, like a minimax search to depth 4, under a name for the
+/// crosstable. Every game gets a new player from the factory, so that games can run on different threads, and no
+/// game's player remembers another game.
+pub struct Entrant<GameType>
+    where GameType: Game
+{
+    pub name: String,
+    factory: PlayerFactory<GameType>
+}
+
+impl<GameType> Entrant<GameType>
+    where GameType: Game
+{
+    pub fn new<FactoryType>(name: &str, factory: FactoryType) -> Entrant<GameType>
+        where FactoryType: Fn(&GameType, usize) -> Box<dyn Player<GameType::Position, GameType::Move>> + Send + Sync + 'static {
+        Entrant {
+            name: name.to_string(),
+            factory: Arc::new(factory)
+        }
+    }
+}
+
+/// Who plays whom in a {@link Tournament}.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Schedule {
+    /// Every entrant plays every other entrant.
+    RoundRobin,
+    /// The given number of rounds, in each of which entrants play someone with about the same score so far, and
+    /// nobody they've played before if that can be helped. With an odd number of entrants, the lowest-ranked entrant
+    /// who hasn't had one yet sits out each round with a bye, which scores as if they'd won every game.
+    Swiss { rounds: usize }
+}
+
+/// A Tournament plays a number of {@link Entrant}s against each other in a game, on a {@link Schedule}, and ranks
+/// them by their scores.
+///
+/// Each pairing is a short {@link Match}, in which the entrants take turns being player 0, and which can start from a
+/// set of openings. The pairings of a round are spread across threads, each of which plays one match at a time. Each
+/// game is seeded by its place in the schedule, so the results don't depend on the number of threads.
+pub struct Tournament<GameType>
+    where GameType: Game
+{
+    game: GameType,
+    entrants: Vec<Entrant<GameType>>,
+    schedule: Schedule,
+    games_per_pairing: usize,
+    openings: Vec<GameType::Position>,
+    threads: usize,
+    seed: u64
+}
+
+impl<GameType> Tournament<GameType>
+    where GameType: Game + Clone + Sync,
+          GameType::Position: Sync + 'static,
+          GameType::Move: 'static
+{
+    /// Set up a round robin between the entrants, with two games per pairing, which runs one thread per available
+    /// CPU core.
+    pub fn new(game: GameType, entrants: Vec<Entrant<GameType>>) -> Tournament<GameType> {
+        Tournament {
+            game,
+            entrants,
+            schedule: Schedule::RoundRobin,
+            games_per_pairing: 2,
+            openings: vec![],
+            threads: thread::available_parallelism().map(NonZeroUsize::get).unwrap_or(1),
+            seed: 0
+        }
+    }
+
+    pub fn with_schedule(mut self, schedule: Schedule) -> Tournament<GameType> {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Play the given number of games in each pairing (at least one). An even number gives each entrant the first
+    /// move equally often.
+    pub fn with_games_per_pairing(mut self, games_per_pairing: usize) -> Tournament<GameType> {
+        self.games_per_pairing = games_per_pairing.max(1);
+        self
+    }
+
+    /// Start the games of each pairing from the given positions instead of the game's initial position, as a
+    /// {@link Match} does.
+    pub fn with_openings(mut self, openings: Vec<GameType::Position>) -> Tournament<GameType> {
+        self.openings = openings;
+        self
+    }
+
+    /// Play the games on the given number of threads (at least one).
+    pub fn with_threads(mut self, threads: usize) -> Tournament<GameType> {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Seed the chance events of the games. Each game gets its own seed, counting up from this one.
+    pub fn with_seed(mut self, seed: u64) -> Tournament<GameType> {
+        self.seed = seed;
+        self
+    }
+
+    /// Play every game of the tournament, and return the results, or the first error that stopped a game.
+    pub fn run(&self) -> Result<TournamentResult, GameEngineError> {
+        let mut result = TournamentResult::new(self.entrants.iter().map(|entrant| entrant.name.clone()).collect());
+        match self.schedule {
+            Schedule::RoundRobin => {
+                let pairings: Vec<(usize, usize)> = (0..self.entrants.len())
+                    .flat_map(|entrant| (entrant + 1..self.entrants.len()).map(move |opponent| (entrant, opponent)))
+                    .collect();
+                self.play_round(&pairings, &mut result)?;
+            },
+            Schedule::Swiss { rounds } => {
+                for _ in 0..rounds {
+                    let pairings = self.swiss_pairings(&mut result);
+                    self.play_round(&pairings, &mut result)?;
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Pair the entrants for the next Swiss round, from the highest score down, and give a bye to one entrant if
+    /// their number is odd.
+    fn swiss_pairings(&self, result: &mut TournamentResult) -> Vec<(usize, usize)> {
+        let mut unpaired: Vec<usize> = result.ranking();
+        if unpaired.len() % 2 == 1 {
+            // the lowest-ranked entrant without a bye sits out, or the lowest-ranked one if everyone has had one
+            let position = (0..unpaired.len()).rev()
+                .find(|&position| result.byes[unpaired[position]] == 0)
+                .unwrap_or(unpaired.len() - 1);
+            let entrant = unpaired.remove(position);
+            result.byes[entrant] += 1;
+            result.bye_points[entrant] += self.games_per_pairing as f64;
+        }
+
+        let mut pairings = vec![];
+        while !unpaired.is_empty() {
+            let entrant = unpaired.remove(0);
+            let position = (0..unpaired.len())
+                .find(|&position| result.games[entrant][unpaired[position]] == 0)
+                .unwrap_or(0);
+            pairings.push((entrant, unpaired.remove(position)));
+        }
+        pairings
+    }
+
+    /// Play the match of each pairing, spread across the tournament's threads, and add their games to the results.
+    fn play_round(&self, pairings: &[(usize, usize)], result: &mut TournamentResult) -> Result<(), GameEngineError> {
+        let first_seed = self.seed.wrapping_add(result.games_played() as u64);
+
+        let next_pairing = AtomicUsize::new(0);
+        let match_results = Mutex::new(vec![Ok(MatchResult::default()); pairings.len()]);
+        thread::scope(|scope| {
+            for _ in 0..self.threads.min(pairings.len()) {
+                scope.spawn(|| loop {
+                    let index = next_pairing.fetch_add(1, Ordering::Relaxed);
+                    if index >= pairings.len() {
+                        break;
+                    }
+                    let seed = first_seed.wrapping_add((index * self.games_per_pairing) as u64);
+                    let match_result = self.pairing_match(pairings[index], seed).play();
+                    match_results.lock().unwrap()[index] = match_result;
+                });
+            }
+        });
+
+        for (&(entrant, opponent), match_result) in pairings.iter().zip(match_results.into_inner().unwrap()) {
+            result.add_match(entrant, opponent, &match_result?);
+        }
+        Ok(())
+    }
+
+    /// The match between the entrants with the given indices, with the first moving first in the match's first game.
+    fn pairing_match(&self, (entrant, opponent): (usize, usize), seed: u64) -> Match<GameType> {
+        Match::between(self.game.clone(), self.entrants[entrant].factory.clone(), self.entrants[opponent].factory.clone())
+            .with_games(self.games_per_pairing)
+            .with_openings(self.openings.clone())
+            .with_seed(seed)
+    }
+}
+
+/// How a tournament went: every entrant's score against every other, from which the crosstable and standings are
+/// made. Entrants are indexed in the order they were entered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TournamentResult {
+    pub names: Vec<String>,
+    /// The points each entrant scored against each other entrant, counting a draw as half a point.
+    pub scores: Vec<Vec<f64>>,
+    /// The number of games each entrant played against each other entrant.
+    pub games: Vec<Vec<usize>>,
+    /// The number of byes each entrant had in a Swiss tournament.
+    pub byes: Vec<usize>,
+    pub bye_points: Vec<f64>
+}
+
+impl TournamentResult {
+    pub fn new(names: Vec<String>) -> TournamentResult {
+        let entrants = names.len();
+        TournamentResult {
+            names,
+            scores: vec![vec![0.0; entrants]; entrants],
+            games: vec![vec![0; entrants]; entrants],
+            byes: vec![0; entrants],
+            bye_points: vec![0.0; entrants]
+        }
+    }
+
+    /// Add a game between the entrants with the given indices, in which the first scored the given points.
+    pub fn add_game(&mut self, entrant: usize, opponent: usize, score: f64) {
+        self.scores[entrant][opponent] += score;
+        self.scores[opponent][entrant] += 1.0 - score;
+        self.games[entrant][opponent] += 1;
+        self.games[opponent][entrant] += 1;
+    }
+
+    /// Add the games of a match between the entrants with the given indices, with its results from the first's point
+    /// of view.
+    pub fn add_match(&mut self, entrant: usize, opponent: usize, match_result: &MatchResult) {
+        let score = match_result.wins as f64 + match_result.draws as f64 / 2.0;
+        self.scores[entrant][opponent] += score;
+        self.scores[opponent][entrant] += match_result.games() as f64 - score;
+        self.games[entrant][opponent] += match_result.games();
+        self.games[opponent][entrant] += match_result.games();
+    }
+
+    pub fn games_played(&self) -> usize {
+        self.games.iter().flatten().sum::<usize>() / 2
+    }
+
+    /// The entrant's total points, including any byes.
+    pub fn points(&self, entrant: usize) -> f64 {
+        self.scores[entrant].iter().sum::<f64>() + self.bye_points[entrant]
+    }
+
+    /// The indices of the entrants, from the most points to the fewest. Entrants with the same points keep the order
+    /// they were entered in.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut ranking: Vec<usize> = (0..self.names.len()).collect();
+        ranking.sort_by(|&a, &b| self.points(b).total_cmp(&self.points(a)));
+        ranking
+    }
+
+    /// Write a table with a row and a column for each entrant, in which each cell holds the points the row's entrant
+    /// scored against the column's, followed by each entrant's total.
+    pub fn write_crosstable<WriterType: Write>(&self, writer: &mut WriterType) -> std::io::Result<()> {
+        let name_width = self.names.iter().map(String::len).max().unwrap_or(0);
+        write!(writer, "{:>4} {:<name_width$}", "", "")?;
+        for column in 1..=self.names.len() {
+            write!(writer, " {:>5}", column)?;
+        }
+        writeln!(writer, " {:>6}", "Total")?;
+
+        for (row, name) in self.names.iter().enumerate() {
+            write!(writer, "{:>3}. {:<name_width$}", row + 1, name)?;
+            for column in 0..self.names.len() {
+                let cell = match (row == column, self.games[row][column]) {
+                    (true, _) => "x".to_string(),
+                    (false, 0) => ".".to_string(),
+                    (false, _) => format!("{:.1}", self.scores[row][column])
+                };
+                write!(writer, " {:>5}", cell)?;
+            }
+            writeln!(writer, " {:>6.1}", self.points(row))?;
+        }
+        Ok(())
+    }
+
+    /// Write the entrants in order of their points, with their points, the games they played, and any byes they had.
+    pub fn write_standings<WriterType: Write>(&self, writer: &mut WriterType) -> std::io::Result<()> {
+        let name_width = self.names.iter().map(String::len).max().unwrap_or(0);
+        for (rank, entrant) in self.ranking().into_iter().enumerate() {
+            let games: usize = self.games[entrant].iter().sum();
+            write!(writer, "{:>3}. {:<name_width$} {:>6.1} / {}", rank + 1, self.names[entrant], self.points(entrant), games)?;
+            match self.byes[entrant] {
+                0 => writeln!(writer)?,
+                byes => writeln!(writer, " + {} bye(s)", byes)?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::Position;
+    use crate::core::move_strategy::{NegamaxMoveStrategy, RandomMoveStrategy};
+    use crate::core::player::DefaultPlayer;
+    use crate::core::test_support::negamax_player;
+    use crate::core::tournament::{Entrant, Schedule, Tournament, TournamentResult};
+    use crate::tictactoe::{Piece, TicTacToe, TicTacToeMove, TicTacToePosition};
+
+    fn entrants() -> Vec<Entrant<TicTacToe>> {
+        vec![
+            Entrant::new("negamax", negamax_player),
+            Entrant::new("random", |game: &TicTacToe, player| Box::new(DefaultPlayer::new(Box::new(RandomMoveStrategy::for_game(game).with_seed(player as u64))))),
+            Entrant::new("negamax depth 1", |game: &TicTacToe, _| Box::new(DefaultPlayer::new(Box::new(NegamaxMoveStrategy::for_game(game).with_max_depth(1)))))
+        ]
+    }
+
+    #[test]
+    pub fn test_round_robin() {
+        let result = Tournament::new(TicTacToe {}, entrants())
+            .with_games_per_pairing(4)
+            .with_threads(3)
+            .run()
+            .unwrap();
+        assert_eq!(result.games_played(), 12);
+        assert_eq!(result.games[0][1], 4);
+        // the full search never loses
+        assert!(result.scores[1][0] == 0.0 && result.scores[2][0] == 0.0);
+        assert_eq!(result.ranking()[0], 0);
+
+        // the threads don't change the results
+        let single_threaded = Tournament::new(TicTacToe {}, entrants())
+            .with_games_per_pairing(4)
+            .with_threads(1)
+            .run()
+            .unwrap();
+        assert_eq!(single_threaded, result);
+    }
+
+    #[test]
+    pub fn test_round_robin_plays_openings() {
+        let negamax = |name| Entrant::new(name, negamax_player);
+        // X in the corner and O on the edge beside it, which loses for O
+        let opening = TicTacToePosition::new()
+            .apply(&TicTacToeMove { position: 0, piece: Piece::X })
+            .apply(&TicTacToeMove { position: 1, piece: Piece::O });
+        let result = Tournament::new(TicTacToe {}, vec![negamax("first"), negamax("second")])
+            .with_games_per_pairing(3)
+            .with_openings(vec![opening])
+            .run()
+            .unwrap();
+        // whoever is X wins, and the first entrant is X in two of the three games
+        assert_eq!(result.scores[0][1], 2.0);
+        assert_eq!(result.scores[1][0], 1.0);
+    }
+
+    #[test]
+    pub fn test_swiss() {
+        let result = Tournament::new(TicTacToe {}, entrants())
+            .with_schedule(Schedule::Swiss { rounds: 3 })
+            .run()
+            .unwrap();
+        // two entrants play and one sits out each round, and nobody sits out twice
+        assert_eq!(result.games_played(), 6);
+        assert_eq!(result.byes, vec![1, 1, 1]);
+        assert!((0..3).all(|entrant| (0..3).all(|opponent| result.games[entrant][opponent] <= 2)));
+    }
+
+    #[test]
+    pub fn test_crosstable_and_standings() {
+        let mut result = TournamentResult::new(vec!["first".to_string(), "second".to_string(), "third".to_string()]);
+        result.add_game(0, 1, 1.0);
+        result.add_game(1, 0, 0.5);
+        result.add_game(1, 2, 1.0);
+        result.byes[2] += 1;
+
+        let mut crosstable = vec![];
+        result.write_crosstable(&mut crosstable).unwrap();
+        assert_eq!(String::from_utf8(crosstable).unwrap(), concat!(
+            "                1     2     3  Total\n",
+            "  1. first      x   1.5     .    1.5\n",
+            "  2. second   0.5     x   1.0    1.5\n",
+            "  3. third      .   0.0     x    0.0\n"));
+
+        let mut standings = vec![];
+        result.write_standings(&mut standings).unwrap();
+        assert_eq!(String::from_utf8(standings).unwrap(), concat!(
+            "  1. first     1.5 / 2\n",
+            "  2. second    1.5 / 3\n",
+            "  3. third     0.0 / 1 + 1 bye(s)\n"));
+    }
+}
@@ -0,0 +1,135 @@
+use crate::core::position_evaluator::PositionEvaluation;
+use crate::core::r#move::Move;
+
+/// A position that can produce a 64-bit hash of itself, for use as a key in a {@link TranspositionTable}.
+///
+/// Equal positions must produce equal hashes, regardless of the order of the moves that led to them. Distinct
+/// positions should produce distinct hashes as far as possible, but collisions are tolerated.
+pub trait PositionHash {
+    fn position_hash(&self) -> u64;
+}
+
+/// The depth recorded for an evaluation whose search reached a terminal position on every line, so that it's
+/// valid no matter how deep a later search wants to look.
+pub const EXHAUSTIVE_DEPTH: usize = usize::MAX;
+
+/// How a stored evaluation relates to the true evaluation of the position.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Bound {
+    /// The evaluation is the position's evaluation at the stored depth.
+    Exact,
+    /// The search was cut off because the evaluation was already too good for the opponent to allow, so the
+    /// position's evaluation is at least the stored evaluation.
+    Lower,
+    /// None of the moves raised the evaluation above what the player could already get elsewhere, so the position's
+    /// evaluation is at most the stored evaluation.
+    Upper
+}
+
+/// What a search learned about a single position.
+#[derive(Copy, Clone, Debug)]
+pub struct TranspositionEntry<MoveType: Move> {
+    pub hash: u64,
+    /// How many plies deep the position was searched.
+    pub depth: usize,
+    pub evaluation: PositionEvaluation,
+    pub bound: Bound,
+    /// The move that gave the evaluation, or None for an {@link Bound#Upper} bound, where no move was any good.
+    pub best_move: Option<MoveType>
+}
+
+/// A fixed-size cache of search results, keyed on position hashes.
+///
+/// Each hash maps to a single slot. When two positions compete for the same slot, the one that was searched more
+/// deeply wins, since it took more work to produce; ties go to the newer entry.
+pub struct TranspositionTable<MoveType: Move> {
+    entries: Vec<Option<TranspositionEntry<MoveType>>>
+}
+
+impl<MoveType: Move> TranspositionTable<MoveType> {
+    pub fn new(capacity: usize) -> TranspositionTable<MoveType> {
+        assert!(capacity > 0, "A transposition table needs room for at least one entry");
+        TranspositionTable {
+            entries: vec![None; capacity]
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the entry stored for the hash, if there is one.
+    pub fn get(&self, hash: u64) -> Option<&TranspositionEntry<MoveType>> {
+        self.entries[self.index(hash)].as_ref().filter(|entry| entry.hash == hash)
+    }
+
+    /// Stores the entry, unless its slot already holds a more deeply searched entry for a different position.
+    pub fn store(&mut self, entry: TranspositionEntry<MoveType>) {
+        let index = self.index(entry.hash);
+        let replace = match &self.entries[index] {
+            None => true,
+            Some(existing) => existing.hash == entry.hash || existing.depth <= entry.depth
+        };
+        if replace {
+            self.entries[index] = Some(entry);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.iter_mut().for_each(|entry| *entry = None);
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.entries.len() as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::position_evaluator::PositionEvaluation;
+    use crate::core::transposition_table::{Bound, TranspositionEntry, TranspositionTable};
+    use crate::tictactoe::{Piece, TicTacToeMove};
+
+    fn entry(hash: u64, depth: usize, position: usize) -> TranspositionEntry<TicTacToeMove> {
+        TranspositionEntry {
+            hash,
+            depth,
+            evaluation: PositionEvaluation::Estimate(0.0),
+            bound: Bound::Exact,
+            best_move: Some(TicTacToeMove { position, piece: Piece::X })
+        }
+    }
+
+    #[test]
+    pub fn test_store_and_get() {
+        let mut table = TranspositionTable::new(16);
+        assert!(table.get(3).is_none());
+
+        table.store(entry(3, 2, 4));
+        assert_eq!(table.get(3).unwrap().best_move.unwrap().position, 4);
+        // same slot, different position
+        assert!(table.get(19).is_none());
+
+        table.clear();
+        assert!(table.get(3).is_none());
+    }
+
+    #[test]
+    pub fn test_replacement_prefers_deeper_entries() {
+        let mut table = TranspositionTable::new(16);
+        table.store(entry(3, 4, 0));
+
+        // a shallower search of a different position doesn't evict the deeper one
+        table.store(entry(19, 2, 1));
+        assert_eq!(table.get(3).unwrap().best_move.unwrap().position, 0);
+        assert!(table.get(19).is_none());
+
+        // a new result for the same position always replaces the old one
+        table.store(entry(3, 1, 2));
+        assert_eq!(table.get(3).unwrap().best_move.unwrap().position, 2);
+
+        // and now the slot is shallow enough to give up
+        table.store(entry(19, 2, 1));
+        assert_eq!(table.get(19).unwrap().best_move.unwrap().position, 1);
+    }
+}
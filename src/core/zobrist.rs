@@ -0,0 +1,77 @@
+use crate::core::random::split_mix_64;
+
+/// A position that describes its contents as a set of (square, piece) features, so that it can be hashed with
+/// {@link ZobristKeys}.
+///
+/// Implementations with big boards are expected to keep their hash up to date in `Position::apply` by toggling only
+/// the features that the move changed, rather than rehashing every feature.
+pub trait ZobristPosition {
+    /// Every (square, piece) pair that's currently on the board.
+    fn features(&self) -> Vec<(usize, usize)>;
+
+    /// The position's hash, which must equal the hash of its features.
+    fn zobrist_hash(&self) -> u64;
+}
+
+/// Random keys for each (square, piece) feature of a board with `SQUARES` squares and `PIECES` kinds of piece.
+///
+/// A position's hash is the XOR of the keys of its features. Since XOR is its own inverse, placing or removing a
+/// piece updates the hash with a single XOR, and two move orders that reach the same position produce the same hash.
+///
+/// The keys are generated at compile time from a fixed seed, so hashes are stable between runs and tables can live
+/// in a `static`.
+pub struct ZobristKeys<const SQUARES: usize, const PIECES: usize> {
+    keys: [[u64; PIECES]; SQUARES]
+}
+
+impl<const SQUARES: usize, const PIECES: usize> ZobristKeys<SQUARES, PIECES> {
+    pub const fn new(seed: u64) -> ZobristKeys<SQUARES, PIECES> {
+        let mut state = seed;
+        let mut keys = [[0; PIECES]; SQUARES];
+        let mut square = 0;
+        while square < SQUARES {
+            let mut piece = 0;
+            while piece < PIECES {
+                let (next_state, key) = split_mix_64(state);
+                state = next_state;
+                keys[square][piece] = key;
+                piece += 1;
+            }
+            square += 1;
+        }
+        ZobristKeys { keys }
+    }
+
+    /// The key for a piece on a square. XOR it into a hash to place the piece, and XOR it again to remove it.
+    pub fn key(&self, square: usize, piece: usize) -> u64 {
+        self.keys[square][piece]
+    }
+
+    /// Hash a whole set of features from scratch.
+    pub fn hash<I: IntoIterator<Item = (usize, usize)>>(&self, features: I) -> u64 {
+        features.into_iter().fold(0, |hash, (square, piece)| hash ^ self.key(square, piece))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::zobrist::ZobristKeys;
+
+    #[test]
+    pub fn test_keys_are_distinct() {
+        let keys = ZobristKeys::<42, 2>::new(1);
+        let mut all_keys: Vec<u64> = (0..42).flat_map(|square| [keys.key(square, 0), keys.key(square, 1)]).collect();
+        all_keys.sort();
+        all_keys.dedup();
+        assert_eq!(all_keys.len(), 84);
+    }
+
+    #[test]
+    pub fn test_hash_is_order_independent() {
+        let keys = ZobristKeys::<9, 2>::new(1);
+        let hash = keys.hash([(0, 0), (4, 1), (8, 0)]);
+        assert_eq!(hash, keys.hash([(8, 0), (0, 0), (4, 1)]));
+        assert_eq!(hash ^ keys.key(8, 0), keys.hash([(0, 0), (4, 1)]));
+        assert_eq!(keys.hash([]), 0);
+    }
+}
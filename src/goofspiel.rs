@@ -0,0 +1,374 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::core::game::{Game, GameOutcome, IllegalMoveError, Position, Oracle};
+use crate::core::observation::{ImperfectInformationGame, ObservationModel};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use crate::core::r#move::{Move, MoveGenerator};
+use crate::core::random::Random;
+
+/// The number of cards in each hand in a standard game of Goofspiel: one suit, ace to king.
+pub const GOOFSPIEL_CARDS: usize = 13;
+
+/// The most cards each hand can hold, since hands are stored as the bits of a u64.
+pub const GOOFSPIEL_MAX_CARDS: usize = 63;
+
+/// Why a game of Goofspiel can't be set up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GoofspielError {
+    /// The hands would hold more than {@link GOOFSPIEL_MAX_CARDS} cards.
+    TooManyCards(usize)
+}
+
+impl Display for GoofspielError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoofspielError::TooManyCards(cards) => write!(f, "Goofspiel can be played with at most {} cards, not {}", GOOFSPIEL_MAX_CARDS, cards)
+        }
+    }
+}
+
+impl Error for GoofspielError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GoofspielPosition {
+    /// The cards in each player's hand, as bits: bit 0 is set if the player holds card 1, and so on. A card stays in
+    /// the hand until the round it's bid in is over, so that the hand doesn't give the bid away.
+    pub hands: [u64; 2],
+    pub scores: [usize; 2],
+    /// The number of rounds already played. The prize in each round is worth one more than the round number.
+    pub round: usize,
+    pub cards: usize,
+    /// Player 0's bid this round, once they've made it.
+    pub bid: Option<usize>
+}
+
+impl GoofspielPosition {
+    /// The start of a game in which each player holds the cards 1 to `cards`, or an error if there are more cards
+    /// than a hand can hold.
+    pub fn new(cards: usize) -> Result<GoofspielPosition, GoofspielError> {
+        match cards {
+            0..=GOOFSPIEL_MAX_CARDS => Ok(GoofspielPosition::deal(cards)),
+            _ => Err(GoofspielError::TooManyCards(cards))
+        }
+    }
+
+    /// The start of a game with a number of cards that's already known to fit in a hand.
+    fn deal(cards: usize) -> GoofspielPosition {
+        GoofspielPosition {
+            hands: [(1 << cards) - 1; 2],
+            scores: [0, 0],
+            round: 0,
+            cards,
+            bid: None
+        }
+    }
+
+    /// The value of the prize being bid for.
+    pub fn prize(&self) -> usize {
+        self.round + 1
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.round == self.cards
+    }
+
+    pub fn holds(&self, player: usize, card: usize) -> bool {
+        (1..=self.cards).contains(&card) && self.hands[player] & (1 << (card - 1)) != 0
+    }
+
+    /// The cards in the player's hand, lowest first.
+    pub fn hand(&self, player: usize) -> Vec<usize> {
+        (1..=self.cards).filter(|&card| self.holds(player, card)).collect()
+    }
+
+    fn player_to_move(&self) -> usize {
+        match self.bid {
+            None => 0,
+            Some(_) => 1
+        }
+    }
+}
+
+/// A player bidding one of the cards in their hand.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GoofspielMove {
+    pub player: usize,
+    pub card: usize
+}
+
+impl Move for GoofspielMove {}
+
+impl Position<GoofspielPosition, GoofspielMove> for GoofspielPosition {
+    fn apply(&self, m: &GoofspielMove) -> GoofspielPosition {
+        let mut new_position = *self;
+        match self.bid {
+            None => new_position.bid = Some(m.card),
+            Some(bid) => {
+                // player 1 has bid too, so the round is over
+                new_position.hands[0] &= !(1 << (bid - 1));
+                new_position.hands[1] &= !(1 << (m.card - 1));
+                if bid > m.card {
+                    new_position.scores[0] += self.prize();
+                } else if m.card > bid {
+                    new_position.scores[1] += self.prize();
+                }
+                new_position.round += 1;
+                new_position.bid = None;
+            }
+        }
+        new_position
+    }
+
+    fn check_move(&self, m: &GoofspielMove) -> Result<(), IllegalMoveError> {
+        if self.is_over() {
+            return Err(IllegalMoveError::GameOver);
+        }
+        if m.player != self.player_to_move() {
+            return Err(IllegalMoveError::WrongSideToMove);
+        }
+        if !self.holds(m.player, m.card) {
+            return Err(IllegalMoveError::CardNotInHand(m.card));
+        }
+        Ok(())
+    }
+}
+
+pub struct GoofspielOracle {}
+
+impl Oracle<GoofspielPosition, GoofspielMove> for GoofspielOracle {
+    fn next_player(&self, game: &GoofspielPosition) -> Option<usize> {
+        match game.is_over() {
+            true => None,
+            false => Some(game.player_to_move())
+        }
+    }
+
+    fn is_terminal(&self, game: &GoofspielPosition) -> bool {
+        game.is_over()
+    }
+
+    fn outcome(&self, game: &GoofspielPosition) -> Option<GameOutcome> {
+        if !game.is_over() {
+            None
+        } else if game.scores[0] > game.scores[1] {
+            Some(GameOutcome::Win(0))
+        } else if game.scores[1] > game.scores[0] {
+            Some(GameOutcome::Win(1))
+        } else {
+            Some(GameOutcome::Draw)
+        }
+    }
+}
+
+pub struct GoofspielMoveGenerator {}
+
+impl MoveGenerator<GoofspielPosition, GoofspielMove> for GoofspielMoveGenerator {
+    fn get_moves(&self, game: &GoofspielPosition) -> Vec<GoofspielMove> {
+        if game.is_over() {
+            return vec![];
+        }
+        let player = game.player_to_move();
+        game.hand(player).into_iter().map(|card| GoofspielMove { player, card }).collect()
+    }
+}
+
+/// Estimates a position by how far ahead the evaluator's player is, as a share of all the prizes, so the estimates
+/// lie between -1 and 1.
+#[derive(Copy, Clone, Debug)]
+pub struct GoofspielPositionEvaluator {
+    pub player: usize
+}
+
+impl PositionEvaluator<GoofspielPosition, GoofspielMove> for GoofspielPositionEvaluator {
+    fn evaluate(&self, position: &GoofspielPosition) -> PositionEvaluation {
+        let lead = position.scores[self.player] as f64 - position.scores[1 - self.player] as f64;
+        if !position.is_over() {
+            let prizes = (position.cards * (position.cards + 1) / 2) as f64;
+            return PositionEvaluation::Estimate(lead / prizes);
+        }
+        if lead > 0.0 {
+            PositionEvaluation::Winning(0)
+        } else if lead < 0.0 {
+            PositionEvaluation::Losing(0)
+        } else {
+            PositionEvaluation::Draw
+        }
+    }
+}
+
+/// What a player sees of a Goofspiel position: everything but the other player's bid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GoofspielObservation {
+    pub player: usize,
+    /// The position, with player 0's bid left out if the observing player can't see it.
+    pub position: GoofspielPosition,
+    pub bid_hidden: bool
+}
+
+pub struct GoofspielObservationModel {}
+
+impl ObservationModel<GoofspielPosition, GoofspielMove> for GoofspielObservationModel {
+    type Observation = GoofspielObservation;
+
+    fn observe(&self, position: &GoofspielPosition, player: usize) -> GoofspielObservation {
+        let bid_hidden = player == 1 && position.bid.is_some();
+        let mut position = *position;
+        if bid_hidden {
+            position.bid = None;
+        }
+        GoofspielObservation { player, position, bid_hidden }
+    }
+
+    /// A hidden bid could be any card in player 0's hand, with nothing to tell them apart.
+    fn determinize(&self, observation: &GoofspielObservation, random: &mut Random) -> GoofspielPosition {
+        let mut position = observation.position;
+        if observation.bid_hidden {
+            let hand = position.hand(0);
+            position.bid = Some(hand[random.below(hand.len())]);
+        }
+        position
+    }
+}
+
+/// Goofspiel, the game of pure strategy: each round, both players secretly bid a card from their hand for a prize,
+/// and the higher bid wins the prize's value in points. Tied bids win nothing. Every card is bid exactly once, and the
+/// player with the most points at the end wins.
+///
+/// Here the prizes come up in order, from 1 to the number of cards, and player 0 bids first. Player 1 can't see the
+/// bid before bidding, so the bids are as good as simultaneous.
+///
+/// Player 0's bid is part of the position, so Goofspiel is not an {@link AlternatingGame}: a search of the whole
+/// position would let player 1 see it. Play it with strategies that only see a player's observation, like
+/// {@link DeterminizedMctsMoveStrategy}.
+#[derive(Copy, Clone, Debug)]
+pub struct Goofspiel {
+    cards: usize
+}
+
+impl Goofspiel {
+    /// A game in which each player holds the cards 1 to `cards`, or an error if there are more cards than a hand can
+    /// hold.
+    pub fn new(cards: usize) -> Result<Goofspiel, GoofspielError> {
+        GoofspielPosition::new(cards).map(|_| Goofspiel { cards })
+    }
+
+    pub fn cards(&self) -> usize {
+        self.cards
+    }
+}
+
+impl Default for Goofspiel {
+    fn default() -> Self {
+        Goofspiel { cards: GOOFSPIEL_CARDS }
+    }
+}
+
+impl Game for Goofspiel {
+    type Position = GoofspielPosition;
+    type Move = GoofspielMove;
+    type MoveGenerator = GoofspielMoveGenerator;
+    type Oracle = GoofspielOracle;
+    type PositionEvaluator = GoofspielPositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> GoofspielPosition {
+        GoofspielPosition::deal(self.cards)
+    }
+
+    fn move_generator(&self) -> GoofspielMoveGenerator {
+        GoofspielMoveGenerator {}
+    }
+
+    fn oracle(&self) -> GoofspielOracle {
+        GoofspielOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> GoofspielPositionEvaluator {
+        GoofspielPositionEvaluator { player }
+    }
+}
+
+impl ImperfectInformationGame for Goofspiel {
+    type ObservationModel = GoofspielObservationModel;
+
+    fn observation_model(&self) -> GoofspielObservationModel {
+        GoofspielObservationModel {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{Game, GameOutcome, IllegalMoveError, Oracle, Position};
+    use crate::core::observation::ObservationModel;
+    use crate::core::random::Random;
+    use crate::goofspiel::{Goofspiel, GoofspielError, GoofspielMove, GoofspielObservationModel, GoofspielOracle, GoofspielPosition};
+
+    #[test]
+    pub fn test_rounds() {
+        let position = GoofspielPosition::new(2).unwrap()
+            .apply(&GoofspielMove { player: 0, card: 1 });
+        assert_eq!(position.hand(0), vec![1, 2]);
+
+        let position = position.apply(&GoofspielMove { player: 1, card: 2 });
+        assert_eq!(position.hands, [0b10, 0b01]);
+        assert_eq!(position.scores, [0, 1]);
+
+        let position = position
+            .apply(&GoofspielMove { player: 0, card: 2 })
+            .apply(&GoofspielMove { player: 1, card: 1 });
+        assert_eq!(position.scores, [2, 1]);
+        assert_eq!(GoofspielOracle {}.outcome(&position), Some(GameOutcome::Win(0)));
+    }
+
+    #[test]
+    pub fn test_large_hands() {
+        let position = GoofspielPosition::new(20).unwrap();
+        assert_eq!(position.hand(1), (1..=20).collect::<Vec<usize>>());
+
+        let position = position
+            .apply(&GoofspielMove { player: 0, card: 20 })
+            .apply(&GoofspielMove { player: 1, card: 17 });
+        assert!(!position.hand(0).contains(&20));
+        assert!(!position.hand(1).contains(&17));
+        assert_eq!(position.scores, [1, 0]);
+    }
+
+    #[test]
+    pub fn test_too_many_cards() {
+        assert_eq!(GoofspielPosition::new(63).map(|position| position.hand(0).len()), Ok(63));
+        assert_eq!(GoofspielPosition::new(64), Err(GoofspielError::TooManyCards(64)));
+        assert_eq!(Goofspiel::new(64).map(|game| game.cards()), Err(GoofspielError::TooManyCards(64)));
+        assert_eq!(Goofspiel::new(5).map(|game| game.initial_position().hand(1)), Ok(vec![1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    pub fn test_check_move() {
+        let position = GoofspielPosition::new(3).unwrap();
+        assert_eq!(position.check_move(&GoofspielMove { player: 0, card: 3 }), Ok(()));
+        assert_eq!(position.check_move(&GoofspielMove { player: 1, card: 3 }), Err(IllegalMoveError::WrongSideToMove));
+        assert_eq!(position.check_move(&GoofspielMove { player: 0, card: 4 }), Err(IllegalMoveError::CardNotInHand(4)));
+    }
+
+    #[test]
+    pub fn test_observation_hides_the_bid() {
+        let observation_model = GoofspielObservationModel {};
+        let start = GoofspielPosition::new(3).unwrap();
+        let low_bid = start.apply(&GoofspielMove { player: 0, card: 1 });
+        let high_bid = start.apply(&GoofspielMove { player: 0, card: 3 });
+
+        assert_eq!(observation_model.observe(&low_bid, 1), observation_model.observe(&high_bid, 1));
+        assert_ne!(observation_model.observe(&low_bid, 0), observation_model.observe(&high_bid, 0));
+
+        // every determinization is a position that looks the same to the observer
+        let observation = observation_model.observe(&low_bid, 1);
+        let mut random = Random::new(0);
+        for _ in 0..10 {
+            let position = observation_model.determinize(&observation, &mut random);
+            assert!(position.bid.is_some());
+            assert_eq!(observation_model.observe(&position, 1), observation);
+        }
+    }
+}
@@ -0,0 +1,6 @@
+pub mod core;
+pub mod tictactoe;
+pub mod connect_four;
+pub mod pig;
+pub mod goofspiel;
+pub mod othello;
@@ -1,31 +1,23 @@
-use crate::core::engine::GameEngine;
-use crate::core::move_strategy::MinimaxMoveStrategy;
-use crate::connect_four::{Piece, ConnectFourPosition, ConnectFourMove, ConnectFourMoveGenerator, ConnectFourOracle, ConnectFourPositionEvaluator};
-use crate::core::player::DefaultPlayer;
+use two_player_games::core::r#match::Match;
+use two_player_games::core::move_strategy::NegamaxMoveStrategy;
+use two_player_games::connect_four::ConnectFour;
+use two_player_games::core::player::{DefaultPlayer, Player};
 
-mod tictactoe;
-mod core;
-mod connect_four;
+const SEARCH_DEPTH: usize = 5;
+const OPPONENT_SEARCH_DEPTH: usize = 3;
+const MATCH_GAMES: usize = 2;
 
 fn main() {
-    let player1 = DefaultPlayer::new(
-        Box::new(MinimaxMoveStrategy::new(ConnectFourPositionEvaluator { player_piece: Piece::Red },
-                                          ConnectFourMoveGenerator {},
-                                          ConnectFourOracle {position_evaluator: ConnectFourPositionEvaluator{player_piece: Piece::Phantom}}))
-    );
-    let player2 = DefaultPlayer::new(
-        Box::new(MinimaxMoveStrategy::new(ConnectFourPositionEvaluator { player_piece: Piece::Black },
-                                          ConnectFourMoveGenerator {},
-                                          ConnectFourOracle {position_evaluator: ConnectFourPositionEvaluator{player_piece: Piece::Phantom}}))
-    );
+    let game = ConnectFour {};
+    let player = |game: &ConnectFour, _| Box::new(DefaultPlayer::new(Box::new(NegamaxMoveStrategy::for_game(game).with_max_depth(SEARCH_DEPTH)))) as Box<dyn Player<_, _>>;
+    let opponent = |game: &ConnectFour, _| Box::new(DefaultPlayer::new(Box::new(NegamaxMoveStrategy::for_game(game).with_max_depth(OPPONENT_SEARCH_DEPTH)))) as Box<dyn Player<_, _>>;
 
-    let mut engine = GameEngine::<ConnectFourPosition, ConnectFourMove, DefaultPlayer<ConnectFourPosition, ConnectFourMove>, ConnectFourOracle>::new(
-        ConnectFourPosition::new(),
-        player1,
-        player2,
-        ConnectFourOracle {
-            position_evaluator: ConnectFourPositionEvaluator{player_piece: Piece::Phantom}
-        });
+    let result = Match::new(game, player, opponent)
+        .with_games(MATCH_GAMES)
+        .play();
 
-    engine.run();
-}
\ No newline at end of file
+    match result {
+        Ok(result) => println!("Depth {} against depth {}: {}", SEARCH_DEPTH, OPPONENT_SEARCH_DEPTH, result),
+        Err(error) => println!("{}", error)
+    }
+}
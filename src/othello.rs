@@ -0,0 +1,332 @@
+use crate::core::game::{AlternatingGame, DeterministicGame, Game, GameOutcome, IllegalMoveError, Position, Oracle};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator, SideToMovePositionEvaluator};
+use crate::core::r#move::{Move, MoveGenerator};
+
+const SIZE: usize = 8;
+
+/// The eight directions a line of discs can run in, as (row, column) steps.
+const DIRECTIONS: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Piece {
+    Black,
+    White
+}
+
+impl Piece {
+    pub fn other(&self) -> Piece {
+        match self {
+            Piece::Black => Piece::White,
+            Piece::White => Piece::Black
+        }
+    }
+}
+
+/// An Othello board, indexed row by row from the top left, so that square `row * 8 + column` is in the given row
+/// and column.
+#[derive(Copy, Clone, Debug)]
+pub struct OthelloPosition {
+    pub board: [Option<Piece>; SIZE * SIZE],
+    pub last_player: usize
+}
+
+impl OthelloPosition {
+    pub fn new() -> OthelloPosition {
+        let mut board = [None; SIZE * SIZE];
+        board[27] = Some(Piece::White);
+        board[28] = Some(Piece::Black);
+        board[35] = Some(Piece::Black);
+        board[36] = Some(Piece::White);
+        // Black always goes first, so the last player is White or 1
+        OthelloPosition::from_board(board, 1)
+    }
+
+    pub fn from_board(board: [Option<Piece>; SIZE * SIZE], last_player: usize) -> OthelloPosition {
+        OthelloPosition { board, last_player }
+    }
+
+    pub fn piece_to_move(&self) -> Piece {
+        match self.last_player {
+            0 => Piece::White,
+            _ => Piece::Black
+        }
+    }
+
+    pub fn count(&self, piece: Piece) -> usize {
+        self.board.iter().filter(|&&square| square == Some(piece)).count()
+    }
+
+    /// The discs that placing the piece on the square would flip. A placement is only legal if it flips something.
+    pub fn flips(&self, square: usize, piece: Piece) -> Vec<usize> {
+        let mut flips = vec![];
+        if square >= SIZE * SIZE || self.board[square].is_some() {
+            return flips;
+        }
+        let (row, column) = ((square / SIZE) as isize, (square % SIZE) as isize);
+        for (row_step, column_step) in DIRECTIONS {
+            let mut line = vec![];
+            let (mut r, mut c) = (row + row_step, column + column_step);
+            while (0..SIZE as isize).contains(&r) && (0..SIZE as isize).contains(&c) {
+                let index = r as usize * SIZE + c as usize;
+                match self.board[index] {
+                    Some(disc) if disc == piece => {
+                        flips.append(&mut line);
+                        break;
+                    },
+                    Some(_) => line.push(index),
+                    None => break
+                }
+                r += row_step;
+                c += column_step;
+            }
+        }
+        flips
+    }
+
+    /// The squares the piece can be placed on.
+    pub fn legal_squares(&self, piece: Piece) -> Vec<usize> {
+        (0..SIZE * SIZE).filter(|&square| !self.flips(square, piece).is_empty()).collect()
+    }
+
+    /// The game is over once neither side can place a disc.
+    pub fn is_over(&self) -> bool {
+        self.legal_squares(Piece::Black).is_empty() && self.legal_squares(Piece::White).is_empty()
+    }
+}
+
+impl Default for OthelloPosition {
+    fn default() -> Self {
+        OthelloPosition::new()
+    }
+}
+
+/// Placing a disc on the square with the given index, or passing, which a player must do when they can't place one.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OthelloMove {
+    Place(usize),
+    Pass
+}
+
+impl Move for OthelloMove {}
+
+impl Position<OthelloPosition, OthelloMove> for OthelloPosition {
+    fn apply(&self, m: &OthelloMove) -> OthelloPosition {
+        let mut new_position = *self;
+        let piece = self.piece_to_move();
+        if let OthelloMove::Place(square) = *m {
+            for flip in self.flips(square, piece) {
+                new_position.board[flip] = Some(piece);
+            }
+            new_position.board[square] = Some(piece);
+        }
+        new_position.last_player = 1 - self.last_player;
+        new_position
+    }
+
+    fn check_move(&self, m: &OthelloMove) -> Result<(), IllegalMoveError> {
+        if self.is_over() {
+            return Err(IllegalMoveError::GameOver);
+        }
+        let piece = self.piece_to_move();
+        match *m {
+            OthelloMove::Place(square) if square >= SIZE * SIZE => Err(IllegalMoveError::OffBoard),
+            OthelloMove::Place(square) if self.board[square].is_some() => Err(IllegalMoveError::SquareOccupied(square)),
+            OthelloMove::Place(square) if self.flips(square, piece).is_empty() => Err(IllegalMoveError::NothingCaptured(square)),
+            OthelloMove::Pass if !self.legal_squares(piece).is_empty() => Err(IllegalMoveError::PassWithMovesLeft),
+            _ => Ok(())
+        }
+    }
+}
+
+pub struct OthelloOracle {}
+
+impl Oracle<OthelloPosition, OthelloMove> for OthelloOracle {
+    fn next_player(&self, game: &OthelloPosition) -> Option<usize> {
+        match game.last_player {
+            0 => Some(1),
+            1 => Some(0),
+            _ => None
+        }
+    }
+
+    fn is_terminal(&self, game: &OthelloPosition) -> bool {
+        game.is_over()
+    }
+
+    /// A player with no placements passes, unless the other player has none either, which ends the game.
+    fn must_pass(&self, game: &OthelloPosition) -> bool {
+        game.legal_squares(game.piece_to_move()).is_empty() && !game.is_over()
+    }
+
+    fn outcome(&self, game: &OthelloPosition) -> Option<GameOutcome> {
+        if !game.is_over() {
+            return None;
+        }
+        let (black, white) = (game.count(Piece::Black), game.count(Piece::White));
+        if black > white {
+            Some(GameOutcome::Win(0))
+        } else if white > black {
+            Some(GameOutcome::Win(1))
+        } else {
+            Some(GameOutcome::Draw)
+        }
+    }
+}
+
+pub struct OthelloMoveGenerator {}
+
+impl MoveGenerator<OthelloPosition, OthelloMove> for OthelloMoveGenerator {
+    fn get_moves(&self, game: &OthelloPosition) -> Vec<OthelloMove> {
+        game.legal_squares(game.piece_to_move()).into_iter().map(OthelloMove::Place).collect()
+    }
+
+    /// The pass, when the oracle says the player must pass.
+    fn get_pass(&self, game: &OthelloPosition) -> Option<OthelloMove> {
+        let must_pass = OthelloOracle {}.must_pass(game);
+        match must_pass {
+            true => Some(OthelloMove::Pass),
+            false => None
+        }
+    }
+}
+
+/// Estimates a position by the evaluator's share of the discs on the board, between -1 and 1.
+#[derive(Copy, Clone, Debug)]
+pub struct OthelloPositionEvaluator {
+    pub player_piece: Piece
+}
+
+impl PositionEvaluator<OthelloPosition, OthelloMove> for OthelloPositionEvaluator {
+    fn evaluate(&self, position: &OthelloPosition) -> PositionEvaluation {
+        let (own, other) = (position.count(self.player_piece) as f64, position.count(self.player_piece.other()) as f64);
+        if !position.is_over() {
+            PositionEvaluation::Estimate((own - other) / (own + other))
+        } else if own > other {
+            PositionEvaluation::Winning(0)
+        } else if other > own {
+            PositionEvaluation::Losing(0)
+        } else {
+            PositionEvaluation::Draw
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct OthelloSideToMovePositionEvaluator {}
+
+impl SideToMovePositionEvaluator<OthelloPosition, OthelloMove> for OthelloSideToMovePositionEvaluator {
+    fn evaluate_for_side_to_move(&self, position: &OthelloPosition) -> PositionEvaluation {
+        OthelloPositionEvaluator { player_piece: position.piece_to_move() }.evaluate(position)
+    }
+}
+
+/// Othello on the standard eight-by-eight board, with Black (player 0) moving first.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Othello {}
+
+impl Game for Othello {
+    type Position = OthelloPosition;
+    type Move = OthelloMove;
+    type MoveGenerator = OthelloMoveGenerator;
+    type Oracle = OthelloOracle;
+    type PositionEvaluator = OthelloPositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> OthelloPosition {
+        OthelloPosition::new()
+    }
+
+    fn move_generator(&self) -> OthelloMoveGenerator {
+        OthelloMoveGenerator {}
+    }
+
+    fn oracle(&self) -> OthelloOracle {
+        OthelloOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> OthelloPositionEvaluator {
+        let player_piece = match player {
+            0 => Piece::Black,
+            _ => Piece::White
+        };
+        OthelloPositionEvaluator { player_piece }
+    }
+}
+
+impl DeterministicGame for Othello {}
+
+impl AlternatingGame for Othello {
+    type SideToMovePositionEvaluator = OthelloSideToMovePositionEvaluator;
+
+    fn side_to_move_position_evaluator(&self) -> OthelloSideToMovePositionEvaluator {
+        OthelloSideToMovePositionEvaluator {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{IllegalMoveError, Oracle, Position};
+    use crate::core::move_strategy::{MoveStrategy, NegamaxMoveStrategy};
+    use crate::core::r#move::MoveGenerator;
+    use crate::othello::{Othello, OthelloMove, OthelloMoveGenerator, OthelloOracle, OthelloPosition, Piece};
+
+    /// White in the corner, and Black next to it, with Black to move: White can capture, but Black can't.
+    fn black_must_pass() -> OthelloPosition {
+        let mut board = [None; 64];
+        board[0] = Some(Piece::White);
+        board[1] = Some(Piece::Black);
+        OthelloPosition::from_board(board, 1)
+    }
+
+    #[test]
+    pub fn test_opening_moves() {
+        let position = OthelloPosition::new();
+        assert_eq!(OthelloMoveGenerator {}.get_moves(&position), vec![OthelloMove::Place(19), OthelloMove::Place(26), OthelloMove::Place(37), OthelloMove::Place(44)]);
+
+        let position = position.apply(&OthelloMove::Place(19));
+        assert_eq!(position.board[27], Some(Piece::Black));
+        assert_eq!((position.count(Piece::Black), position.count(Piece::White)), (4, 1));
+    }
+
+    #[test]
+    pub fn test_pass() {
+        let move_generator = OthelloMoveGenerator {};
+        let position = black_must_pass();
+        assert!(!OthelloOracle {}.is_terminal(&position));
+        assert!(OthelloOracle {}.must_pass(&position));
+        assert!(!OthelloOracle {}.must_pass(&OthelloPosition::new()));
+        assert!(move_generator.get_moves(&position).is_empty());
+        assert_eq!(move_generator.get_moves_or_pass(&position), vec![OthelloMove::Pass]);
+
+        let position = position.apply(&OthelloMove::Pass);
+        assert_eq!(OthelloOracle {}.next_player(&position), Some(1));
+        assert!(!OthelloOracle {}.must_pass(&position));
+        assert_eq!(move_generator.get_pass(&position), None);
+        assert_eq!(move_generator.get_moves(&position), vec![OthelloMove::Place(2)]);
+    }
+
+    #[test]
+    pub fn test_check_move() {
+        let position = OthelloPosition::new();
+        assert_eq!(position.check_move(&OthelloMove::Place(19)), Ok(()));
+        assert_eq!(position.check_move(&OthelloMove::Place(27)), Err(IllegalMoveError::SquareOccupied(27)));
+        assert_eq!(position.check_move(&OthelloMove::Place(0)), Err(IllegalMoveError::NothingCaptured(0)));
+        assert_eq!(position.check_move(&OthelloMove::Place(64)), Err(IllegalMoveError::OffBoard));
+        assert_eq!(position.check_move(&OthelloMove::Pass), Err(IllegalMoveError::PassWithMovesLeft));
+        assert_eq!(black_must_pass().check_move(&OthelloMove::Pass), Ok(()));
+    }
+
+    #[test]
+    pub fn test_strategies_pass_when_they_must() {
+        let negamax = NegamaxMoveStrategy::for_game(&Othello {}).with_max_depth(3);
+        assert_eq!(negamax.choose_move(&black_must_pass()), Some(OthelloMove::Pass));
+
+        // once White takes the last capture, nobody can move, and there's nothing to choose
+        let finished = black_must_pass().apply(&OthelloMove::Pass).apply(&OthelloMove::Place(2));
+        assert!(OthelloOracle {}.is_terminal(&finished));
+        assert_eq!(negamax.choose_move(&finished), None);
+    }
+}
@@ -0,0 +1,242 @@
+use crate::core::game::{Game, GameOutcome, IllegalMoveError, Position, Oracle};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use crate::core::r#move::{Move, MoveGenerator};
+
+/// The score to reach in a standard game of Pig.
+pub const PIG_TARGET: usize = 100;
+
+#[derive(Copy, Clone, Debug)]
+pub struct PigPosition {
+    pub scores: [usize; 2],
+    /// The points rolled so far this turn, which the player to move banks by holding, and loses by rolling a 1.
+    pub turn_total: usize,
+    pub player_to_move: usize,
+    /// Whether the player to move has just rolled the die, so that the position is waiting on the roll.
+    pub rolling: bool,
+    pub target: usize
+}
+
+impl PigPosition {
+    /// The start of a game played to the given score, with player 0 to move.
+    pub fn new(target: usize) -> PigPosition {
+        PigPosition {
+            scores: [0, 0],
+            turn_total: 0,
+            player_to_move: 0,
+            rolling: false,
+            target
+        }
+    }
+
+    /// The player who has reached the target score, if either has.
+    pub fn winner(&self) -> Option<usize> {
+        (0..2).find(|&player| self.scores[player] >= self.target)
+    }
+}
+
+/// A player either rolls the die or holds, and then the die comes up with one of its faces.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PigMove {
+    Roll,
+    Hold,
+    /// The die coming up with the given face. This is a chance event rather than a player's move.
+    Die(usize)
+}
+
+impl Move for PigMove {}
+
+impl Position<PigPosition, PigMove> for PigPosition {
+    fn apply(&self, m: &PigMove) -> PigPosition {
+        let mut new_position = *self;
+        match *m {
+            PigMove::Roll => new_position.rolling = true,
+            PigMove::Hold => {
+                new_position.scores[self.player_to_move] += self.turn_total;
+                new_position.turn_total = 0;
+                new_position.player_to_move = 1 - self.player_to_move;
+            },
+            PigMove::Die(1) => {
+                new_position.rolling = false;
+                new_position.turn_total = 0;
+                new_position.player_to_move = 1 - self.player_to_move;
+            },
+            PigMove::Die(face) => {
+                new_position.rolling = false;
+                new_position.turn_total += face;
+            }
+        }
+        new_position
+    }
+
+    /// While the die is rolling, only a die face can be played, and until it's rolled, a die face can't be.
+    fn check_move(&self, m: &PigMove) -> Result<(), IllegalMoveError> {
+        if self.winner().is_some() {
+            return Err(IllegalMoveError::GameOver);
+        }
+        match (m, self.rolling) {
+            (PigMove::Die(face), true) if (1..=6).contains(face) => Ok(()),
+            (PigMove::Die(_), true) => Err(IllegalMoveError::ImpossibleChanceEvent),
+            (PigMove::Die(_), false) => Err(IllegalMoveError::NotAChanceNode),
+            (PigMove::Roll | PigMove::Hold, false) => Ok(()),
+            (PigMove::Roll | PigMove::Hold, true) => Err(IllegalMoveError::AwaitingChanceEvent)
+        }
+    }
+}
+
+pub struct PigOracle {}
+
+impl Oracle<PigPosition, PigMove> for PigOracle {
+    /// No player is to move while the die is rolling.
+    fn next_player(&self, game: &PigPosition) -> Option<usize> {
+        match self.is_terminal(game) || game.rolling {
+            true => None,
+            false => Some(game.player_to_move)
+        }
+    }
+
+    fn is_terminal(&self, game: &PigPosition) -> bool {
+        game.winner().is_some()
+    }
+
+    fn outcome(&self, game: &PigPosition) -> Option<GameOutcome> {
+        game.winner().map(GameOutcome::Win)
+    }
+}
+
+pub struct PigMoveGenerator {}
+
+impl MoveGenerator<PigPosition, PigMove> for PigMoveGenerator {
+    fn get_moves(&self, game: &PigPosition) -> Vec<PigMove> {
+        match game.rolling {
+            true => vec![],
+            false => vec![PigMove::Roll, PigMove::Hold]
+        }
+    }
+
+    fn get_chance_events(&self, game: &PigPosition) -> Vec<(PigMove, f64)> {
+        match game.rolling {
+            true => (1..=6).map(|face| (PigMove::Die(face), 1.0 / 6.0)).collect(),
+            false => vec![]
+        }
+    }
+}
+
+/// Estimates a position by how far ahead of the other player the evaluator's player is, as a share of the target
+/// score, so the estimates lie between -1 and 1. Points rolled this turn count for the player to move, even though
+/// they aren't banked yet.
+#[derive(Copy, Clone, Debug)]
+pub struct PigPositionEvaluator {
+    pub player: usize
+}
+
+impl PositionEvaluator<PigPosition, PigMove> for PigPositionEvaluator {
+    fn evaluate(&self, position: &PigPosition) -> PositionEvaluation {
+        match position.winner() {
+            Some(winner) if winner == self.player => PositionEvaluation::Winning(0),
+            Some(_) => PositionEvaluation::Losing(0),
+            None => {
+                let mut scores = position.scores;
+                scores[position.player_to_move] += position.turn_total;
+                let lead = scores[self.player] as f64 - scores[1 - self.player] as f64;
+                PositionEvaluation::Estimate((lead / position.target as f64).clamp(-1.0, 1.0))
+            }
+        }
+    }
+}
+
+/// Pig, the dice game: players take turns rolling a die as often as they like, adding up what they roll, until they
+/// hold and bank the total, or roll a 1 and lose it. The first player to bank the target score wins.
+#[derive(Copy, Clone, Debug)]
+pub struct Pig {
+    pub target: usize
+}
+
+impl Default for Pig {
+    fn default() -> Self {
+        Pig { target: PIG_TARGET }
+    }
+}
+
+impl Game for Pig {
+    type Position = PigPosition;
+    type Move = PigMove;
+    type MoveGenerator = PigMoveGenerator;
+    type Oracle = PigOracle;
+    type PositionEvaluator = PigPositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> PigPosition {
+        PigPosition::new(self.target)
+    }
+
+    fn move_generator(&self) -> PigMoveGenerator {
+        PigMoveGenerator {}
+    }
+
+    fn oracle(&self) -> PigOracle {
+        PigOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> PigPositionEvaluator {
+        PigPositionEvaluator { player }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::game::{GameOutcome, IllegalMoveError, Oracle, Position};
+    use crate::core::r#move::MoveGenerator;
+    use crate::pig::{PigMove, PigMoveGenerator, PigOracle, PigPosition};
+
+    #[test]
+    pub fn test_turns() {
+        let position = PigPosition::new(10)
+            .apply(&PigMove::Roll)
+            .apply(&PigMove::Die(5))
+            .apply(&PigMove::Roll)
+            .apply(&PigMove::Die(4));
+        assert_eq!(position.turn_total, 9);
+        assert_eq!(position.player_to_move, 0);
+
+        let held = position.apply(&PigMove::Hold);
+        assert_eq!(held.scores, [9, 0]);
+        assert_eq!(held.player_to_move, 1);
+
+        let busted = position.apply(&PigMove::Roll).apply(&PigMove::Die(1));
+        assert_eq!(busted.scores, [0, 0]);
+        assert_eq!(busted.turn_total, 0);
+        assert_eq!(busted.player_to_move, 1);
+
+        let won = position.apply(&PigMove::Roll).apply(&PigMove::Die(2)).apply(&PigMove::Hold);
+        assert_eq!(PigOracle {}.outcome(&won), Some(GameOutcome::Win(0)));
+    }
+
+    #[test]
+    pub fn test_chance_events() {
+        let move_generator = PigMoveGenerator {};
+        let position = PigPosition::new(10);
+        assert!(move_generator.get_chance_events(&position).is_empty());
+
+        let rolling = position.apply(&PigMove::Roll);
+        assert!(move_generator.get_moves(&rolling).is_empty());
+        assert_eq!(PigOracle {}.next_player(&rolling), None);
+        let chance_events = move_generator.get_chance_events(&rolling);
+        assert_eq!(chance_events.len(), 6);
+        assert!((chance_events.iter().map(|&(_, probability)| probability).sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    pub fn test_check_move() {
+        let position = PigPosition::new(10);
+        assert_eq!(position.check_move(&PigMove::Roll), Ok(()));
+        assert_eq!(position.check_move(&PigMove::Die(3)), Err(IllegalMoveError::NotAChanceNode));
+
+        let rolling = position.apply(&PigMove::Roll);
+        assert_eq!(rolling.check_move(&PigMove::Die(3)), Ok(()));
+        assert_eq!(rolling.check_move(&PigMove::Die(7)), Err(IllegalMoveError::ImpossibleChanceEvent));
+        assert_eq!(rolling.check_move(&PigMove::Hold), Err(IllegalMoveError::AwaitingChanceEvent));
+    }
+}
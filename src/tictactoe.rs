@@ -1,14 +1,59 @@
-use crate::core::game::{Position, Oracle};
-use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator};
+use crate::core::game::{AlternatingGame, DeterministicGame, Game, GameOutcome, IllegalMoveError, Position, Oracle, UnmakePosition};
+use crate::core::position_evaluator::{PositionEvaluation, PositionEvaluator, SideToMovePositionEvaluator};
 use crate::r#core::r#move::{Move,MoveGenerator};
+use crate::core::transposition_table::PositionHash;
+use crate::core::zobrist::{ZobristKeys, ZobristPosition};
 
+static ZOBRIST_KEYS: ZobristKeys<9, 2> = ZobristKeys::new(0x7469637461637465);
+
+/// A tic-tac-toe board, indexed row by row from the top left.
 #[derive(Copy, Clone, Debug)]
 pub struct TicTacToePosition {
     pub board: [Option<Piece>; 9],
     pub last_player: usize,
 }
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+impl TicTacToePosition {
+    pub fn new() -> TicTacToePosition {
+        // X always goes first, so the last player is O or 1
+        TicTacToePosition::from_board([None; 9], 1)
+    }
+
+    pub fn from_board(board: [Option<Piece>; 9], last_player: usize) -> TicTacToePosition {
+        TicTacToePosition {
+            board,
+            last_player,
+        }
+    }
+}
+
+impl TicTacToePosition {
+    /// Whether the given piece has three in a row anywhere on the board.
+    #[allow(clippy::needless_return)]
+    pub fn has_three_in_a_row(&self, piece: Piece) -> bool {
+        // this is kind of dumb, but let's just hardcode all possible win conditions
+        let is_three_in_a_row = |x: usize, y: usize, z: usize| {
+            self.board[x] == Some(piece) && self.board[y] == Some(piece) && self.board[z] == Some(piece)
+        };
+
+        return is_three_in_a_row(0, 1, 2)
+            || is_three_in_a_row(3, 4, 5)
+            || is_three_in_a_row(6, 7, 8)
+            || is_three_in_a_row(0, 3, 6)
+            || is_three_in_a_row(1, 4, 7)
+            || is_three_in_a_row(2, 5, 8)
+            || is_three_in_a_row(0, 4, 8)
+            || is_three_in_a_row(2, 4, 6);
+    }
+}
+
+impl Default for TicTacToePosition {
+    fn default() -> Self {
+        TicTacToePosition::new()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Piece {
     X,
     O,
@@ -17,13 +62,67 @@ pub enum Piece {
 impl Position<TicTacToePosition, TicTacToeMove> for TicTacToePosition {
     fn apply(&self, m: &TicTacToeMove) -> TicTacToePosition {
         let mut new_position = *self;
-        new_position.board[m.position] = Some(m.piece);
-        new_position.last_player = (new_position.last_player + 1) % 2;
+        new_position.make_move(m);
         new_position
     }
+
+    fn check_move(&self, m: &TicTacToeMove) -> Result<(), IllegalMoveError> {
+        if self.has_three_in_a_row(Piece::X) || self.has_three_in_a_row(Piece::O) || self.board.iter().all(Option::is_some) {
+            return Err(IllegalMoveError::GameOver);
+        }
+        let piece_to_move = match self.last_player {
+            0 => Piece::O,
+            _ => Piece::X
+        };
+        if m.piece != piece_to_move {
+            return Err(IllegalMoveError::WrongSideToMove);
+        }
+        if m.position >= 9 {
+            return Err(IllegalMoveError::OffBoard);
+        }
+        if self.board[m.position].is_some() {
+            return Err(IllegalMoveError::SquareOccupied(m.position));
+        }
+        Ok(())
+    }
 }
 
-#[derive(Copy, Clone, Debug)]
+/// Undoing a move needs the player who moved before it, and whatever was on the square the move played into.
+impl UnmakePosition<TicTacToeMove> for TicTacToePosition {
+    type Undo = (usize, Option<Piece>);
+
+    fn make_move(&mut self, m: &TicTacToeMove) -> (usize, Option<Piece>) {
+        let undo = (self.last_player, self.board[m.position]);
+        self.board[m.position] = Some(m.piece);
+        self.last_player = (self.last_player + 1) % 2;
+        undo
+    }
+
+    fn unmake_move(&mut self, m: &TicTacToeMove, (last_player, overwritten_piece): (usize, Option<Piece>)) {
+        self.board[m.position] = overwritten_piece;
+        self.last_player = last_player;
+    }
+}
+
+impl ZobristPosition for TicTacToePosition {
+    fn features(&self) -> Vec<(usize, usize)> {
+        (0..9).filter_map(|square| self.board[square].map(|piece| (square, piece as usize))).collect()
+    }
+
+    /// The board is small enough to hash from scratch, which leaves its fields free to set directly. The side to
+    /// move follows from the board, so only the pieces are hashed.
+    fn zobrist_hash(&self) -> u64 {
+        ZOBRIST_KEYS.hash(self.features())
+    }
+}
+
+impl PositionHash for TicTacToePosition {
+    fn position_hash(&self) -> u64 {
+        self.zobrist_hash()
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct TicTacToeMove {
     pub position: usize,
     pub piece: Piece,
@@ -42,40 +141,23 @@ impl Oracle<TicTacToePosition, TicTacToeMove> for TicTacToeOracle {
         }
     }
 
-    fn is_terminal(&self, game: &TicTacToePosition) -> bool {
-        // this is kind of dumb, but let's just hardcode all possible win conditions
-        let is_three_in_a_row = |x: usize, y: usize, z: usize| {
-            game.board[x].is_some() && game.board[y].is_some() && game.board[z].is_some()
-                && game.board[x].unwrap() == game.board[y].unwrap() && game.board[y].unwrap() == game.board[z].unwrap()
-        };
-
-        let is_cat_game = || {
-            game.board[0].is_some()
-                && game.board[1].is_some()
-                && game.board[2].is_some()
-                && game.board[3].is_some()
-                && game.board[4].is_some()
-                && game.board[5].is_some()
-                && game.board[6].is_some()
-                && game.board[7].is_some()
-                && game.board[8].is_some()
-        };
-
-        return is_three_in_a_row(0, 1, 2)
-            || is_three_in_a_row(3, 4, 5)
-            || is_three_in_a_row(6, 7, 8)
-            || is_three_in_a_row(0, 3, 6)
-            || is_three_in_a_row(1, 4, 7)
-            || is_three_in_a_row(2, 5, 8)
-            || is_three_in_a_row(0, 4, 8)
-            || is_three_in_a_row(2, 4, 6)
-            || is_cat_game();
+    fn outcome(&self, game: &TicTacToePosition) -> Option<GameOutcome> {
+        if game.has_three_in_a_row(Piece::X) {
+            Some(GameOutcome::Win(0))
+        } else if game.has_three_in_a_row(Piece::O) {
+            Some(GameOutcome::Win(1))
+        } else if game.board.iter().all(Option::is_some) {
+            Some(GameOutcome::Draw)
+        } else {
+            None
+        }
     }
 }
 
 pub struct TicTacToeMoveGenerator {}
 
 impl MoveGenerator<TicTacToePosition, TicTacToeMove> for TicTacToeMoveGenerator {
+    #[allow(clippy::single_match)]
     fn get_moves(&self, game: &TicTacToePosition) -> Vec<TicTacToeMove> {
         let piece = match game.last_player {
             0 => Piece::O,
@@ -93,57 +175,99 @@ impl MoveGenerator<TicTacToePosition, TicTacToeMove> for TicTacToeMoveGenerator
     }
 }
 
+#[derive(Copy, Clone, Debug)]
 pub struct TicTacToePositionEvaluator {
     pub player_piece: Piece,
 }
 
-impl TicTacToePositionEvaluator {
-    fn has_three_in_a_row(&self, position: &TicTacToePosition, piece: Piece) -> bool {
-        let is_three_in_a_row = |p: Piece, x: usize, y: usize, z: usize| {
-            position.board[x].is_some() && position.board[y].is_some() && position.board[z].is_some()
-                && position.board[x].unwrap() == p && position.board[y].unwrap() == p && position.board[z].unwrap() == p
-        };
-
-        return is_three_in_a_row(piece, 0, 1, 2)
-            || is_three_in_a_row(piece, 3, 4, 5)
-            || is_three_in_a_row(piece, 6, 7, 8)
-            || is_three_in_a_row(piece, 0, 3, 6)
-            || is_three_in_a_row(piece, 1, 4, 7)
-            || is_three_in_a_row(piece, 2, 5, 8)
-            || is_three_in_a_row(piece, 0, 4, 8)
-            || is_three_in_a_row(piece, 2, 4, 6);
-    }
-}
-
 impl PositionEvaluator<TicTacToePosition, TicTacToeMove> for TicTacToePositionEvaluator {
+    #[allow(clippy::needless_return)]
     fn evaluate(&self, position: &TicTacToePosition) -> PositionEvaluation {
         let other_piece = match self.player_piece {
             Piece::X => Piece::O,
             _ => Piece::X
         };
 
-        if self.has_three_in_a_row(position, self.player_piece) {
-            return PositionEvaluation::Winning;
-        } else if self.has_three_in_a_row(position, other_piece) {
-            return PositionEvaluation::Losing;
+        if position.has_three_in_a_row(self.player_piece) {
+            return PositionEvaluation::Winning(0);
+        } else if position.has_three_in_a_row(other_piece) {
+            return PositionEvaluation::Losing(0);
+        } else if position.board.iter().all(Option::is_some) {
+            return PositionEvaluation::Draw;
         } else {
             return PositionEvaluation::Estimate(0.0);
         }
     }
 }
 
+#[derive(Copy, Clone, Debug)]
+pub struct TicTacToeSideToMovePositionEvaluator {}
+
+impl SideToMovePositionEvaluator<TicTacToePosition, TicTacToeMove> for TicTacToeSideToMovePositionEvaluator {
+    fn evaluate_for_side_to_move(&self, position: &TicTacToePosition) -> PositionEvaluation {
+        let player_piece = match position.last_player {
+            0 => Piece::O,
+            _ => Piece::X
+        };
+        TicTacToePositionEvaluator { player_piece }.evaluate(position)
+    }
+}
+
+/// Tic-tac-toe, with X (player 0) moving first.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct TicTacToe {}
+
+impl Game for TicTacToe {
+    type Position = TicTacToePosition;
+    type Move = TicTacToeMove;
+    type MoveGenerator = TicTacToeMoveGenerator;
+    type Oracle = TicTacToeOracle;
+    type PositionEvaluator = TicTacToePositionEvaluator;
+
+    fn player_count(&self) -> usize {
+        2
+    }
+
+    fn initial_position(&self) -> TicTacToePosition {
+        TicTacToePosition::new()
+    }
+
+    fn move_generator(&self) -> TicTacToeMoveGenerator {
+        TicTacToeMoveGenerator {}
+    }
+
+    fn oracle(&self) -> TicTacToeOracle {
+        TicTacToeOracle {}
+    }
+
+    fn position_evaluator(&self, player: usize) -> TicTacToePositionEvaluator {
+        let player_piece = match player {
+            0 => Piece::X,
+            _ => Piece::O
+        };
+        TicTacToePositionEvaluator { player_piece }
+    }
+}
+
+impl DeterministicGame for TicTacToe {}
+
+impl AlternatingGame for TicTacToe {
+    type SideToMovePositionEvaluator = TicTacToeSideToMovePositionEvaluator;
+
+    fn side_to_move_position_evaluator(&self) -> TicTacToeSideToMovePositionEvaluator {
+        TicTacToeSideToMovePositionEvaluator {}
+    }
+}
 
 #[cfg(test)]
 mod tests {
-    use crate::core::game::{Oracle, Position};
-    use crate::tictactoe::{Piece, TicTacToePosition, TicTacToeMove, TicTacToeOracle, TicTacToePlayer};
+    use crate::core::game::{GameOutcome, IllegalMoveError, Oracle, Position, UnmakePosition};
+    use crate::core::zobrist::ZobristPosition;
+    use crate::tictactoe::{Piece, TicTacToePosition, TicTacToeMove, TicTacToeOracle, ZOBRIST_KEYS};
 
     #[test]
     pub fn test_apply() {
-        let game = TicTacToePosition {
-            board: [None; 9],
-            last_player: 1,
-        };
+        let game = TicTacToePosition::new();
 
         let new_game = game.apply(&TicTacToeMove { position: 0, piece: Piece::X });
 
@@ -159,6 +283,66 @@ mod tests {
         assert!(new_game.board[8].is_none());
     }
 
+    #[test]
+    pub fn test_check_move() {
+        let game = TicTacToePosition::new().apply(&TicTacToeMove { position: 4, piece: Piece::X });
+        assert_eq!(game.check_move(&TicTacToeMove { position: 0, piece: Piece::O }), Ok(()));
+        assert_eq!(game.check_move(&TicTacToeMove { position: 0, piece: Piece::X }), Err(IllegalMoveError::WrongSideToMove));
+        assert_eq!(game.check_move(&TicTacToeMove { position: 4, piece: Piece::O }), Err(IllegalMoveError::SquareOccupied(4)));
+        assert_eq!(game.check_move(&TicTacToeMove { position: 9, piece: Piece::O }), Err(IllegalMoveError::OffBoard));
+        assert!(game.try_apply(&TicTacToeMove { position: 4, piece: Piece::O }).is_err());
+
+        //  X | X | X
+        // -----------
+        //  O | O |
+        // -----------
+        //    |   |
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), Some(Piece::X), Some(Piece::O), Some(Piece::O), None, None, None, None], 0);
+        assert_eq!(game.check_move(&TicTacToeMove { position: 5, piece: Piece::O }), Err(IllegalMoveError::GameOver));
+    }
+
+    #[test]
+    pub fn test_make_and_unmake_move() {
+        let mut game = TicTacToePosition::new().apply(&TicTacToeMove { position: 0, piece: Piece::X });
+        let before = game;
+        let r#move = TicTacToeMove { position: 4, piece: Piece::O };
+
+        let undo = game.make_move(&r#move);
+        assert_eq!(game.board, before.apply(&r#move).board);
+        assert_eq!(game.zobrist_hash(), before.apply(&r#move).zobrist_hash());
+        game.unmake_move(&r#move, undo);
+        assert_eq!(game.board, before.board);
+        assert_eq!(game.last_player, before.last_player);
+        assert_eq!(game.zobrist_hash(), before.zobrist_hash());
+
+        // even a move onto an occupied square is taken back cleanly
+        let r#move = TicTacToeMove { position: 0, piece: Piece::O };
+        let undo = game.make_move(&r#move);
+        game.unmake_move(&r#move, undo);
+        assert_eq!(game.board, before.board);
+        assert_eq!(game.zobrist_hash(), before.zobrist_hash());
+    }
+
+    #[test]
+    pub fn test_apply_updates_hash() {
+        let game = TicTacToePosition::new();
+        assert_eq!(game.zobrist_hash(), 0);
+
+        let x_first = game.apply(&TicTacToeMove { position: 0, piece: Piece::X })
+            .apply(&TicTacToeMove { position: 4, piece: Piece::O })
+            .apply(&TicTacToeMove { position: 8, piece: Piece::X });
+        let x_last = game.apply(&TicTacToeMove { position: 8, piece: Piece::X })
+            .apply(&TicTacToeMove { position: 4, piece: Piece::O })
+            .apply(&TicTacToeMove { position: 0, piece: Piece::X });
+        assert_eq!(x_first.zobrist_hash(), x_last.zobrist_hash());
+        assert_eq!(x_first.zobrist_hash(), ZOBRIST_KEYS.hash(x_first.features()));
+
+        let o_corner = game.apply(&TicTacToeMove { position: 0, piece: Piece::X })
+            .apply(&TicTacToeMove { position: 8, piece: Piece::O });
+        assert_ne!(o_corner.zobrist_hash(), x_first.zobrist_hash());
+        assert_eq!(o_corner.zobrist_hash(), ZOBRIST_KEYS.hash(o_corner.features()));
+    }
+
     #[test]
     pub fn test_is_terminal() {
         let mut game = TicTacToePosition {
@@ -243,4 +427,26 @@ mod tests {
         game.board = [Some(Piece::X), Some(Piece::O), Some(Piece::X), Some(Piece::X), Some(Piece::O), Some(Piece::O), Some(Piece::O), Some(Piece::X), Some(Piece::O)];
         assert!(oracle.is_terminal(&game));
     }
+
+    #[test]
+    pub fn test_outcome() {
+        let oracle = TicTacToeOracle {};
+        assert_eq!(oracle.outcome(&TicTacToePosition::new()), None);
+
+        //  X | X | O
+        // -----------
+        //    | O |
+        // -----------
+        //  O |   | X
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::X), Some(Piece::O), None, Some(Piece::O), None, Some(Piece::O), None, Some(Piece::X)], 1);
+        assert_eq!(oracle.outcome(&game), Some(GameOutcome::Win(1)));
+
+        //  X | O | X
+        // -----------
+        //  X | O | O
+        // -----------
+        //  O | X | O
+        let game = TicTacToePosition::from_board([Some(Piece::X), Some(Piece::O), Some(Piece::X), Some(Piece::X), Some(Piece::O), Some(Piece::O), Some(Piece::O), Some(Piece::X), Some(Piece::O)], 1);
+        assert_eq!(oracle.outcome(&game), Some(GameOutcome::Draw));
+    }
 }
\ No newline at end of file